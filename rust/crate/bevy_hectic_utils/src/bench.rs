@@ -0,0 +1,1450 @@
+use std::{collections::HashMap, panic::{self, AssertUnwindSafe, UnwindSafe}, time::{Duration, Instant}, ops::{Add, Deref, DerefMut, Div, Mul, Sub}, hint::black_box, fmt, str::FromStr, num::ParseIntError, error::Error};
+
+/// Number of times to call a measured predicate
+///
+/// [`Default`] is 100000, which may be enough for most cases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Times(u64);
+
+impl Deref for Times {
+    type Target = u64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Times {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Times> for u64 {
+    fn from(times: Times) -> Self {
+        times.0
+    }
+}
+
+impl From<Times> for usize {
+    fn from(times: Times) -> Self {
+        times.0 as usize
+    }
+}
+
+impl From<Times> for u32 {
+    fn from(times: Times) -> Self {
+        times.0 as u32
+    }
+}
+
+impl From<Times> for i32 {
+    fn from(times: Times) -> Self {
+        times.0 as i32
+    }
+}
+
+impl From<u64> for Times {
+    fn from(times: u64) -> Self {
+        Self(times)
+    }
+}
+
+impl From<usize> for Times {
+    fn from(times: usize) -> Self {
+        Self(times as u64)
+    }
+}
+
+impl From<u32> for Times {
+    fn from(times: u32) -> Self {
+        Self(times as u64)
+    }
+}
+
+impl From<i32> for Times {
+    fn from(times: i32) -> Self {
+        Self(times as u64)
+    }
+}
+
+impl Default for Times {
+    /// Value that may be enough for most cases
+    fn default() -> Self {
+        Self(100000)
+    }
+}
+
+impl fmt::Display for Times {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Error returned when parsing [`Times`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTimesError {
+    /// Number of times is negative
+    Negative,
+    /// String is not a number that fits `u64`
+    Invalid(ParseIntError),
+}
+
+impl fmt::Display for ParseTimesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Negative => write!(f, "number of times can't be negative"),
+            Self::Invalid(error) => write!(f, "invalid number of times: {error}"),
+        }
+    }
+}
+
+impl Error for ParseTimesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Negative => None,
+            Self::Invalid(error) => Some(error),
+        }
+    }
+}
+
+impl FromStr for Times {
+    type Err = ParseTimesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start().starts_with('-') {
+            return Err(ParseTimesError::Negative);
+        }
+        s.parse().map(Self).map_err(ParseTimesError::Invalid)
+    }
+}
+
+impl Times {
+    /// Number of times as `u64`
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Checked addition, `None` on overflow
+    pub fn checked_add(self, rhs: impl Into<Times>) -> Option<Times> {
+        self.0.checked_add(rhs.into().0).map(Self)
+    }
+
+    /// Checked multiplication, `None` on overflow
+    pub fn checked_mul(self, rhs: u64) -> Option<Times> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// [`Times::default`] in release builds, but only 1000 with `debug_assertions` to keep debug test runs fast
+    pub fn default_for_profile() -> Times {
+        if cfg!(debug_assertions) {
+            Self(1000)
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Number of times from environment variable `var`, e.g. `BENCH_TIMES=1000000`
+    ///
+    /// Falls back to `default` if the variable is absent, or logs a warning and falls back if it isn't a valid number
+    pub fn from_env(var: &str, default: Times) -> Times {
+        let Ok(value) = std::env::var(var) else {
+            return default;
+        };
+        value.parse().unwrap_or_else(|error| {
+            ::log::warn!("invalid {var}={value:?} ({error}), using {default}");
+            default
+        })
+    }
+
+    /// Counts from `start` to `end` inclusive, `step` apart
+    ///
+    /// Panics if `step` is zero
+    pub fn sweep(start: Times, end: Times, step: u64) -> impl Iterator<Item = Times> {
+        assert!(step != 0, "sweep step can't be zero");
+        (start.0..=end.0).step_by(usize::try_from(step).unwrap_or(usize::MAX)).map(Self)
+    }
+}
+
+impl<T: Into<Times>> Add<T> for Times {
+    type Output = Times;
+
+    /// Saturates at `u64::MAX`
+    fn add(self, rhs: T) -> Self::Output {
+        Self(self.0.saturating_add(rhs.into().0))
+    }
+}
+
+impl<T: Into<Times>> Sub<T> for Times {
+    type Output = Times;
+
+    /// Saturates at zero
+    fn sub(self, rhs: T) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.into().0))
+    }
+}
+
+impl Mul<u64> for Times {
+    type Output = Times;
+
+    /// Saturates at `u64::MAX`
+    fn mul(self, rhs: u64) -> Self::Output {
+        Self(self.0.saturating_mul(rhs))
+    }
+}
+
+impl Div<u64> for Times {
+    type Output = Times;
+
+    fn div(self, rhs: u64) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+/// Mean nanoseconds of `total` over `times` calls, zero if there were no calls
+///
+/// Computed in floating point, integer division would truncate means of fast predicates to zero
+fn mean_nanos(total: Duration, times: Times) -> f64 {
+    if times.0 == 0 {
+        return 0.0;
+    }
+    total.as_nanos() as f64 / times.0 as f64
+}
+
+/// Mean of `total` over `times` calls rounded to nanoseconds, zero if there were no calls
+fn mean(total: Duration, times: Times) -> Duration {
+    Duration::from_nanos(mean_nanos(total, times).round() as u64)
+}
+
+/// Measure mean time of predicate
+///
+/// Zero `times` yields [`Duration::ZERO`]
+pub fn measure_time<F, R>(predicate: F, times: Times) -> Duration
+where
+    F: FnMut() -> R,
+{
+    Duration::from_nanos(measure_time_nanos(predicate, times).round() as u64)
+}
+
+/// Measure mean time of predicate in nanoseconds, keeping sub-nanosecond precision lost by [`Duration`]
+///
+/// The whole loop is timed at once, so reading the clock doesn't dominate the mean of fast predicates.
+/// Zero `times` yields `0.0`
+pub fn measure_time_nanos<F, R>(mut predicate: F, times: Times) -> f64
+where
+    F: FnMut() -> R,
+{
+    let start = Instant::now();
+    for _ in 0..*times {
+        black_box(predicate());
+    }
+    mean_nanos(start.elapsed(), times)
+}
+
+/// Measure mean time of predicate, catching a panic instead of losing the measurement
+///
+/// Returns the number of the call that panicked, counting from 1, as the error, e.g. `Err(5)` if the 5th call
+/// panicked. The panic is still reported by the panic hook
+pub fn measure_time_catching<F, R>(mut predicate: F, times: Times) -> Result<Duration, usize>
+where
+    F: FnMut() -> R + UnwindSafe,
+{
+    let mut total = Duration::ZERO;
+    for call in 1..=*times {
+        let start = Instant::now();
+        // `F: UnwindSafe` makes calling it through `&mut` unwind safe too
+        let result = panic::catch_unwind(AssertUnwindSafe(|| black_box(predicate())));
+        total += start.elapsed();
+        if result.is_err() {
+            return Err(usize::try_from(call).unwrap_or(usize::MAX));
+        }
+    }
+    Ok(mean(total, times))
+}
+
+/// Measure wall time of `threads` threads each calling predicate `times_each` times, e.g. to spot contention
+///
+/// Compare with the wall time of a single thread: a closure scaling perfectly takes just as long on more threads
+pub fn measure_parallel<F, R>(predicate: F, threads: usize, times_each: Times) -> Duration
+where
+    F: Fn() -> R + Sync,
+{
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..*times_each {
+                    black_box(predicate());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+/// Measure mean time of predicate for each number of times, see [`Times::sweep`]
+pub fn measure_sweep<F, R>(mut predicate: F, counts: impl IntoIterator<Item = Times>) -> Vec<(Times, Duration)>
+where
+    F: FnMut() -> R,
+{
+    counts
+        .into_iter()
+        .map(|times| (times, measure_time(&mut predicate, times)))
+        .collect()
+}
+
+/// Measure time of a single call of predicate, returning its result too
+pub fn timed<F, R>(predicate: F) -> (R, Duration)
+where
+    F: FnOnce() -> R,
+{
+    let start = Instant::now();
+    let result = predicate();
+    (result, start.elapsed())
+}
+
+/// Measure mean time of predicate after calling it `warmup` times without measuring
+pub fn measure_time_with_warmup<F, R>(mut predicate: F, warmup: Times, times: Times) -> Duration
+where
+    F: FnMut() -> R,
+{
+    for _ in 0..*warmup {
+        black_box(predicate());
+    }
+    measure_time(predicate, times)
+}
+
+/// Number of predicate calls between clock reads in [`measure_until`]
+const CLOCK_CHECK_INTERVAL: u64 = 16;
+
+/// Call predicate until `budget` elapses, returning number of calls and their mean time
+///
+/// Clock is read every 16 calls to limit overhead, so the budget can be exceeded by up to 16 calls
+pub fn measure_until<F, R>(mut predicate: F, budget: Duration) -> (Times, Duration)
+where
+    F: FnMut() -> R,
+{
+    let start = Instant::now();
+    let mut times = Times(0);
+    loop {
+        for _ in 0..CLOCK_CHECK_INTERVAL {
+            black_box(predicate());
+        }
+        times = times + CLOCK_CHECK_INTERVAL;
+        let elapsed = start.elapsed();
+        if elapsed >= budget {
+            return (times, mean(elapsed, times));
+        }
+    }
+}
+
+/// Measure time of each call of predicate
+///
+/// Result of predicate is passed through [`black_box`], so the call can't be optimized away
+pub fn measure_samples<F, R>(mut predicate: F, times: Times) -> Vec<Duration>
+where
+    F: FnMut() -> R,
+{
+    (0..*times)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(predicate());
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Measure mean time of routine, excluding time spent in setup producing its input
+pub fn measure_time_setup<S, T, F, R>(mut setup: S, mut routine: F, times: Times) -> Duration
+where
+    S: FnMut() -> T,
+    F: FnMut(T) -> R,
+{
+    let mut total = Duration::ZERO;
+    for _ in 0..*times {
+        let input = setup();
+        let start = Instant::now();
+        black_box(routine(input));
+        total += start.elapsed();
+    }
+    mean(total, times)
+}
+
+/// Ratio of `a` to `b`, 1 if both are zero and infinite if only `b` is
+fn ratio(a: Duration, b: Duration) -> f64 {
+    match (a.is_zero(), b.is_zero()) {
+        (true, true) => 1.0,
+        (false, true) => f64::INFINITY,
+        _ => a.as_secs_f64() / b.as_secs_f64(),
+    }
+}
+
+/// Measure mean times of two predicates and ratio of the first to the second
+///
+/// Calls are interleaved, so drift of system load affects both predicates alike.
+/// Ratio greater than 1 means that `b` is faster than `a`. Zero `times` yields zero means and a ratio of 1, and
+/// the ratio is infinite if only `b` measures no time
+pub fn measure_time_compare<A, B, RA, RB>(mut a: A, mut b: B, times: Times) -> (Duration, Duration, f64)
+where
+    A: FnMut() -> RA,
+    B: FnMut() -> RB,
+{
+    let mut a_total = Duration::ZERO;
+    let mut b_total = Duration::ZERO;
+    for _ in 0..*times {
+        let start = Instant::now();
+        black_box(a());
+        a_total += start.elapsed();
+
+        let start = Instant::now();
+        black_box(b());
+        b_total += start.elapsed();
+    }
+    (mean(a_total, times), mean(b_total, times), ratio(a_total, b_total))
+}
+
+/// Measure mean time of futures returned by predicate
+///
+/// Futures are awaited one after another, so no particular runtime is assumed:
+/// the measurement runs on whatever executor awaits it (e.g. `futures::executor::block_on`)
+#[cfg(feature = "async")]
+pub async fn measure_time_async<F, Fut>(mut predicate: F, times: Times) -> Duration
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let mut total = Duration::ZERO;
+    for _ in 0..*times {
+        let start = Instant::now();
+        predicate().await;
+        total += start.elapsed();
+    }
+    mean(total, times)
+}
+
+/// Measure mean CPU time of predicate, to compare against wall time of [`measure_time`]
+///
+/// CPU time is of the whole process, so other busy threads (e.g. tests running in parallel) inflate it,
+/// and time spent blocked (sleeping, waiting for IO) doesn't count.
+/// Supported on Unix-like systems and Windows, panics if the CPU clock can't be read
+#[cfg(feature = "cpu-time")]
+pub fn measure_cpu_time<F, R>(mut predicate: F, times: Times) -> Duration
+where
+    F: FnMut() -> R,
+{
+    let start = cpu_time::ProcessTime::now();
+    for _ in 0..*times {
+        black_box(predicate());
+    }
+    mean(start.elapsed(), times)
+}
+
+/// Global allocator counting allocations for [`measure_allocs`], delegating to [`System`](std::alloc::System)
+///
+/// Has to be installed by the final binary (or test), otherwise nothing is counted:
+///
+/// ```rust
+/// use bevy_hectic_utils::bench::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+#[cfg(feature = "count-allocs")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocs")]
+static ALLOCATIONS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+#[cfg(feature = "count-allocs")]
+static ALLOCATED_BYTES: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+#[cfg(feature = "count-allocs")]
+impl CountingAllocator {
+    fn record(size: usize) {
+        use std::sync::atomic::Ordering;
+
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot() -> AllocStats {
+        use std::sync::atomic::Ordering;
+
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        Self::record(layout.size());
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        Self::record(layout.size());
+        std::alloc::System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size);
+        std::alloc::System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations made by all calls of a predicate, see [`measure_allocs`]
+#[cfg(feature = "count-allocs")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included
+    pub allocations: u64,
+    /// Number of bytes requested by the allocations
+    pub bytes: u64,
+}
+
+/// Count allocations made by predicate called `times` times
+///
+/// Requires [`CountingAllocator`] to be installed as the global allocator, otherwise everything is zero.
+/// Allocations of all threads are counted, so other busy threads (e.g. tests running in parallel) inflate it
+#[cfg(feature = "count-allocs")]
+pub fn measure_allocs<F, R>(mut predicate: F, times: Times) -> AllocStats
+where
+    F: FnMut() -> R,
+{
+    let start = CountingAllocator::snapshot();
+    for _ in 0..*times {
+        black_box(predicate());
+    }
+    let end = CountingAllocator::snapshot();
+    AllocStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+    }
+}
+
+/// Number of operations per second, given time of one operation
+///
+/// Infinite if the operation takes no time
+pub fn throughput(per_op: Duration) -> f64 {
+    if per_op.is_zero() {
+        return f64::INFINITY;
+    }
+    1.0 / per_op.as_secs_f64()
+}
+
+/// Measure number of predicate calls per second
+pub fn measure_throughput<F, R>(predicate: F, times: Times) -> f64
+where
+    F: FnMut() -> R,
+{
+    throughput(measure_time(predicate, times))
+}
+
+/// Summary of a set of timing samples
+///
+/// With the `serde` feature it (de)serializes as an object of whole nanoseconds:
+/// `{"min_ns":…,"max_ns":…,"mean_ns":…,"p50_ns":…,"std_dev_ns":…}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "TimingStatsNanos", from = "TimingStatsNanos")
+)]
+pub struct TimingStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    /// Population standard deviation
+    pub std_dev: Duration,
+}
+
+impl TimingStats {
+    /// Compute statistics of samples, all of them are zero if there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self {
+                min: Duration::ZERO,
+                max: Duration::ZERO,
+                mean: Duration::ZERO,
+                median: Duration::ZERO,
+                std_dev: Duration::ZERO,
+            };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+
+        let total = sorted.iter().sum::<Duration>();
+        let times = Times(count as u64);
+        let mean_nanos = mean_nanos(total, times);
+        let median = if count % 2 == 0 {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_nanos() as f64 - mean_nanos).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Self {
+            min: sorted[0],
+            max: sorted[count - 1],
+            mean: mean(total, times),
+            median,
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+
+    /// Coefficient of variation, standard deviation relative to the mean, e.g. `0.05` for 5% noise
+    ///
+    /// Zero if the mean is zero, which means that every sample is zero
+    pub fn cv(&self) -> f64 {
+        if self.mean.is_zero() {
+            return 0.0;
+        }
+        self.std_dev.as_nanos() as f64 / self.mean.as_nanos() as f64
+    }
+}
+
+/// Serialized form of [`TimingStats`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TimingStatsNanos {
+    min_ns: u64,
+    max_ns: u64,
+    mean_ns: u64,
+    p50_ns: u64,
+    std_dev_ns: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<TimingStats> for TimingStatsNanos {
+    fn from(stats: TimingStats) -> Self {
+        let nanos = |duration: Duration| duration.as_nanos() as u64;
+        Self {
+            min_ns: nanos(stats.min),
+            max_ns: nanos(stats.max),
+            mean_ns: nanos(stats.mean),
+            p50_ns: nanos(stats.median),
+            std_dev_ns: nanos(stats.std_dev),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TimingStatsNanos> for TimingStats {
+    fn from(nanos: TimingStatsNanos) -> Self {
+        Self {
+            min: Duration::from_nanos(nanos.min_ns),
+            max: Duration::from_nanos(nanos.max_ns),
+            mean: Duration::from_nanos(nanos.mean_ns),
+            median: Duration::from_nanos(nanos.p50_ns),
+            std_dev: Duration::from_nanos(nanos.std_dev_ns),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TimingStats {
+    /// Serialize as a JSON object of nanoseconds, e.g. `{"min_ns":10,"max_ns":30,"mean_ns":20,"p50_ns":20,"std_dev_ns":8}`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("`TimingStats` always serializes")
+    }
+}
+
+/// Write labeled statistics to `path` as a JSON array of objects like [`TimingStats::to_json`] with a `label` field
+///
+/// ```json
+/// [{"label":"insert","min_ns":10,"max_ns":30,"mean_ns":20,"p50_ns":20,"std_dev_ns":8}]
+/// ```
+#[cfg(feature = "serde")]
+pub fn write_report(path: impl AsRef<std::path::Path>, results: &[(&str, TimingStats)]) -> std::io::Result<()> {
+    #[derive(serde::Serialize)]
+    struct Labeled<'a> {
+        label: &'a str,
+        #[serde(flatten)]
+        stats: TimingStats,
+    }
+
+    let report: Vec<_> = results.iter().map(|&(label, stats)| Labeled { label, stats }).collect();
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer(file, &report).map_err(std::io::Error::from)
+}
+
+/// Measure statistics of predicate calls
+pub fn measure_stats<F, R>(predicate: F, times: Times) -> TimingStats
+where
+    F: FnMut() -> R,
+{
+    TimingStats::from_samples(&measure_samples(predicate, times))
+}
+
+/// Number of samples taken between checks of confidence interval in [`measure_adaptive`]
+const ADAPTIVE_BATCH: u64 = 32;
+
+/// Measure statistics of predicate calls, sampling until the 95% confidence interval of the mean is tight
+///
+/// Samples are taken in batches of 32, after each batch sampling stops if width of the interval relative
+/// to the mean is at most `target_rel_ci` (e.g. `0.05` for ±2.5%), or if `max` samples are taken
+pub fn measure_adaptive<F, R>(mut predicate: F, target_rel_ci: f64, max: Times) -> TimingStats
+where
+    F: FnMut() -> R,
+{
+    let mut samples = Vec::new();
+    while (samples.len() as u64) < max.get() {
+        let batch = ADAPTIVE_BATCH.min(max.get() - samples.len() as u64);
+        samples.extend(measure_samples(&mut predicate, Times(batch)));
+
+        let stats = TimingStats::from_samples(&samples);
+        let ci_width = 2.0 * 1.96 * stats.std_dev.as_secs_f64() / (samples.len() as f64).sqrt();
+        if ci_width <= target_rel_ci * stats.mean.as_secs_f64() {
+            return stats;
+        }
+    }
+    TimingStats::from_samples(&samples)
+}
+
+/// Percentile `p` (from 0 to 100) of samples using nearest-rank method, zero if there are no samples
+pub fn percentile(samples: &[Duration], p: f64) -> Duration {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    nearest_rank(&sorted, p)
+}
+
+fn nearest_rank(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p.clamp(0.0, 100.0) * sorted.len() as f64 / 100.0).ceil() as usize;
+    sorted[rank.max(1) - 1]
+}
+
+/// Measure p50, p90 and p99 of predicate call times
+pub fn measure_percentiles<F, R>(predicate: F, times: Times) -> (Duration, Duration, Duration)
+where
+    F: FnMut() -> R,
+{
+    let mut samples = measure_samples(predicate, times);
+    samples.sort();
+    (
+        nearest_rank(&samples, 50.0),
+        nearest_rank(&samples, 90.0),
+        nearest_rank(&samples, 99.0),
+    )
+}
+
+/// Accumulates time spent in labeled sections, e.g. the stages of a pipeline
+///
+/// A section is timed between [`Profiler::start`] and [`Profiler::stop`], or for the lifetime of the guard
+/// returned by [`Profiler::scope`]. Guards dereference to the profiler, so scopes can be nested. Sections with
+/// the same label add up.
+///
+/// ```rust
+/// use bevy_hectic_utils::bench::Profiler;
+///
+/// let mut profiler = Profiler::new();
+/// {
+///     let mut frame = profiler.scope("frame");
+///     let _physics = frame.scope("physics");
+///     // ...
+/// }
+///
+/// let report = profiler.report();
+/// assert!(report["frame"] >= report["physics"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Profiler {
+    totals: HashMap<String, Duration>,
+    running: HashMap<String, Instant>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start timing a section, restarting it if it is already running
+    pub fn start(&mut self, label: impl Into<String>) {
+        self.running.insert(label.into(), Instant::now());
+    }
+
+    /// Stop timing a section, returning its elapsed time or [`None`] if it wasn't started
+    pub fn stop(&mut self, label: &str) -> Option<Duration> {
+        let (label, start) = self.running.remove_entry(label)?;
+        let elapsed = start.elapsed();
+        *self.totals.entry(label).or_default() += elapsed;
+        Some(elapsed)
+    }
+
+    /// Time a section until the returned guard is dropped
+    pub fn scope(&mut self, label: impl Into<String>) -> ProfilerScope<'_> {
+        let label = label.into();
+        self.start(label.clone());
+        ProfilerScope { profiler: self, label }
+    }
+
+    /// Total time of every stopped section by label
+    pub fn report(&self) -> HashMap<String, Duration> {
+        self.totals.clone()
+    }
+}
+
+/// Guard of a [`Profiler`] section, stopping it on drop
+#[derive(Debug)]
+pub struct ProfilerScope<'a> {
+    profiler: &'a mut Profiler,
+    label: String,
+}
+
+impl Deref for ProfilerScope<'_> {
+    type Target = Profiler;
+
+    fn deref(&self) -> &Self::Target {
+        self.profiler
+    }
+}
+
+impl DerefMut for ProfilerScope<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.profiler
+    }
+}
+
+impl Drop for ProfilerScope<'_> {
+    fn drop(&mut self) {
+        self.profiler.stop(&self.label);
+    }
+}
+
+/// Format duration with two decimals in the largest unit (ns, µs, ms or s) keeping it at least 1
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    format!("{value:.2} {unit}")
+}
+
+/// Measure statistics of a block and log a one-line report of them at info level
+///
+/// The report includes the coefficient of variation, see [`TimingStats::cv`], a high one means the
+/// measurement is too noisy to trust. Returns [`TimingStats`] of the block
+///
+/// ```rust
+/// use bevy_hectic_utils::bench;
+///
+/// let stats = bench!("sum", 1000, { (0..100u64).sum::<u64>() });
+/// assert!(stats.min <= stats.max);
+/// ```
+#[macro_export]
+macro_rules! bench {
+    ($label: expr, $times: expr, $body: block) => {{
+        let samples = $crate::bench::measure_samples(|| $body, $crate::Times::from($times));
+        let stats = $crate::bench::TimingStats::from_samples(&samples);
+        $crate::__private::log::info!(
+            "{}: mean={:?} p99={:?} cv={:.1}% ops/s={:.0}",
+            $label,
+            stats.mean,
+            $crate::bench::percentile(&samples, 99.0),
+            stats.cv() * 100.0,
+            $crate::bench::throughput(stats.mean),
+        );
+        stats
+    }};
+}
+
+/// Assert that mean time of closure calls is at most `max_per_op`, see [`measure_time`]
+///
+/// Panic message includes the measured and the allowed time
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use bevy_hectic_utils::assert_faster_than;
+///
+/// assert_faster_than!(|| (0..100u64).sum::<u64>(), Duration::from_millis(10), 1000);
+/// ```
+#[macro_export]
+macro_rules! assert_faster_than {
+    ($predicate: expr, $max_per_op: expr, $times: expr $(,)?) => {{
+        let measured = $crate::bench::measure_time($predicate, $crate::Times::from($times));
+        let allowed: ::core::time::Duration = $max_per_op;
+        assert!(
+            measured <= allowed,
+            "{} took {} per op, allowed {}",
+            stringify!($predicate),
+            $crate::bench::format_duration(measured),
+            $crate::bench::format_duration(allowed),
+        );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::test_env::{lock_env, EnvVar};
+
+    fn sleep() {
+        thread::sleep(Duration::from_millis(2));
+    }
+
+    #[test]
+    fn measure_samples_records_each_call() {
+        let samples = measure_samples(sleep, Times::from(5));
+
+        assert_eq!(samples.len(), 5);
+        assert!(samples.iter().all(|sample| *sample >= Duration::from_millis(2)));
+    }
+
+    #[test]
+    fn measure_time_is_mean_of_samples() {
+        let mut calls = 0;
+        let times = Times::from(5);
+        let samples = measure_samples(|| calls += 1, times);
+        assert_eq!(calls, 5);
+        assert_eq!(samples.len(), 5);
+
+        let total = samples.iter().sum::<Duration>();
+        let truncated = total / samples.len() as u32;
+        let measured = mean(total, times);
+
+        // Rounding to nanoseconds exceeds the truncated mean by at most one nanosecond
+        let difference = measured.max(truncated) - measured.min(truncated);
+        assert!(difference <= Duration::from_nanos(1), "{truncated:?} vs {measured:?}");
+    }
+
+    #[test]
+    fn measure_time_with_warmup_runs_warmup_and_times() {
+        let mut calls = 0;
+        measure_time_with_warmup(|| calls += 1, Times::from(3), Times::from(7));
+
+        assert_eq!(calls, 10);
+    }
+
+    #[test]
+    fn timed_returns_result_and_duration() {
+        let (result, duration) = timed(|| {
+            sleep();
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert!(duration >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn measure_time_accepts_mutating_closure() {
+        let mut sum = 0;
+        measure_time(|| sum += 2, Times::from(5));
+
+        assert_eq!(sum, 10);
+    }
+
+    #[test]
+    fn throughput_of_known_duration() {
+        assert_eq!(throughput(Duration::from_millis(4)), 250.0);
+        assert_eq!(throughput(Duration::ZERO), f64::INFINITY);
+    }
+
+    #[test]
+    fn measure_throughput_of_sleep() {
+        let rate = measure_throughput(sleep, Times::from(5));
+
+        assert!(rate > 50.0 && rate <= 500.0, "{rate}");
+    }
+
+    #[test]
+    fn timing_stats_from_known_samples() {
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_millis);
+        let stats = TimingStats::from_samples(&samples);
+
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.max, Duration::from_millis(9));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_micros(4500));
+        assert_eq!(stats.std_dev, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn timing_stats_mean_rounds_like_measure_time() {
+        let samples = [1, 2, 2].map(Duration::from_nanos);
+        let stats = TimingStats::from_samples(&samples);
+
+        // Truncating 5ns over 3 samples would give 1ns
+        assert_eq!(stats.mean, Duration::from_nanos(2));
+        assert_eq!(stats.mean, mean(samples.iter().sum(), Times::from(3)));
+    }
+
+    #[test]
+    fn percentile_of_known_samples() {
+        let samples = (1..=100).rev().map(Duration::from_millis).collect::<Vec<_>>();
+
+        assert_eq!(percentile(&samples, 50.0), Duration::from_millis(50));
+        assert_eq!(percentile(&samples, 90.0), Duration::from_millis(90));
+        assert_eq!(percentile(&samples, 99.0), Duration::from_millis(99));
+        assert_eq!(percentile(&samples, 0.0), Duration::from_millis(1));
+        assert_eq!(percentile(&samples, 100.0), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let samples = [15, 20, 35, 40, 50].map(Duration::from_millis);
+
+        assert_eq!(percentile(&samples, 30.0), Duration::from_millis(20));
+        assert_eq!(percentile(&samples, 40.0), Duration::from_millis(20));
+        assert_eq!(percentile(&samples, 50.0), Duration::from_millis(35));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn measure_percentiles_are_ordered() {
+        let (p50, p90, p99) = measure_percentiles(sleep, Times::from(10));
+
+        assert!(p50 >= Duration::from_millis(2));
+        assert!(p50 <= p90 && p90 <= p99);
+    }
+
+    #[test]
+    fn measure_stats_summarizes_samples() {
+        let stats = measure_stats(sleep, Times::from(3));
+
+        assert!(stats.min >= Duration::from_millis(2));
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+
+    #[test]
+    fn format_duration_picks_unit() {
+        assert_eq!(format_duration(Duration::ZERO), "0.00 ns");
+        assert_eq!(format_duration(Duration::from_nanos(1)), "1.00 ns");
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999.00 ns");
+        assert_eq!(format_duration(Duration::from_nanos(456_000)), "456.00 µs");
+        assert_eq!(format_duration(Duration::from_micros(1234)), "1.23 ms");
+        assert_eq!(format_duration(Duration::from_millis(2000)), "2.00 s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "125.00 s");
+    }
+
+    #[test]
+    fn measure_until_respects_budget() {
+        let budget = Duration::from_millis(100);
+        let start = Instant::now();
+        let (times, mean) = measure_until(sleep, budget);
+        let elapsed = start.elapsed();
+
+        // At most 50 sleeps fit the budget, plus the rest of the last batch
+        assert!(times.get() % CLOCK_CHECK_INTERVAL == 0, "{times}");
+        assert!(times.get() >= CLOCK_CHECK_INTERVAL && times.get() <= 50 + CLOCK_CHECK_INTERVAL, "{times}");
+        assert!(mean >= Duration::from_millis(2));
+        assert!(elapsed >= budget && elapsed < budget + Duration::from_millis(100), "{elapsed:?}");
+    }
+
+    #[test]
+    fn measure_until_counts_fast_calls() {
+        let mut calls = 0;
+        let (times, _) = measure_until(|| calls += 1, Duration::from_millis(10));
+
+        assert_eq!(times.get(), calls);
+        assert!(calls > 1000, "{calls}");
+    }
+
+    #[test]
+    fn measure_adaptive_stops_when_stable() {
+        let mut calls = 0;
+        let stats = measure_adaptive(
+            || {
+                calls += 1;
+                sleep()
+            },
+            0.5,
+            Times::from(1000),
+        );
+
+        assert!(calls < 1000, "{calls}");
+        assert!(stats.mean >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn measure_adaptive_hits_max_when_unstable() {
+        let mut calls = 0;
+        measure_adaptive(
+            || {
+                calls += 1;
+                if calls % 2 == 0 {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            },
+            0.0001,
+            Times::from(40),
+        );
+
+        assert_eq!(calls, 40);
+    }
+
+    #[test]
+    fn assert_faster_than_fast_closure() {
+        crate::assert_faster_than!(|| (0..100u64).sum::<u64>(), Duration::from_millis(10), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "per op, allowed 1.00 ms")]
+    fn assert_faster_than_slow_closure() {
+        crate::assert_faster_than!(sleep, Duration::from_millis(1), 3);
+    }
+
+    #[test]
+    fn bench_returns_stats() {
+        let mut calls = 0;
+        let stats = crate::bench!("sleep", 3, {
+            calls += 1;
+            sleep()
+        });
+
+        assert_eq!(calls, 3);
+        assert!(stats.min >= Duration::from_millis(2));
+        assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+    }
+
+    #[test]
+    fn measure_time_of_pure_closure_is_nonzero() {
+        let measured = measure_time(|| (0..1000u64).sum::<u64>(), Times::from(100));
+
+        assert!(measured > Duration::ZERO);
+    }
+
+    #[test]
+    fn measure_time_compare_reports_slower_first() {
+        let (slow, fast, ratio) = measure_time_compare(sleep, || (), Times::from(5));
+
+        assert!(slow > fast);
+        assert!(ratio > 1.0);
+    }
+
+    #[test]
+    fn measure_time_compare_zero_times() {
+        assert_eq!(measure_time_compare(sleep, sleep, Times(0)), (Duration::ZERO, Duration::ZERO, 1.0));
+        assert_eq!(ratio(Duration::from_millis(6), Duration::from_millis(4)), 1.5);
+        assert_eq!(ratio(Duration::from_millis(6), Duration::ZERO), f64::INFINITY);
+        assert_eq!(ratio(Duration::ZERO, Duration::from_millis(4)), 0.0);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn measure_time_async_awaits_each_future() {
+        let mut calls = 0;
+        let measured = futures::executor::block_on(measure_time_async(
+            || {
+                calls += 1;
+                async { sleep() }
+            },
+            Times::from(3),
+        ));
+
+        assert_eq!(calls, 3);
+        assert!(measured >= Duration::from_millis(2));
+    }
+
+    #[cfg(feature = "cpu-time")]
+    #[test]
+    fn measure_cpu_time_of_busy_loop_is_nonzero() {
+        let measured = measure_cpu_time(|| (0..100_000u64).map(black_box).sum::<u64>(), Times::from(100));
+
+        assert!(measured > Duration::ZERO);
+        assert_eq!(measure_cpu_time(|| (), Times::from(0)), Duration::ZERO);
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn measure_allocs_counts_vec() {
+        let stats = measure_allocs(|| Vec::<u64>::with_capacity(128), Times::from(10));
+
+        assert!(stats.allocations >= 10, "{stats:?}");
+        assert!(stats.bytes >= 10 * 128 * 8, "{stats:?}");
+    }
+
+    #[test]
+    fn measure_time_setup_excludes_setup() {
+        let measured = measure_time_setup(
+            || {
+                thread::sleep(Duration::from_millis(5));
+                vec![1, 2, 3]
+            },
+            |input| input.len(),
+            Times::from(3),
+        );
+
+        assert!(measured < Duration::from_millis(1));
+    }
+
+    #[test]
+    fn get() {
+        assert_eq!(Times::from(42).get(), 42);
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(Times::from(5) + 100, Times::from(105));
+        assert_eq!(Times::from(5) + Times::from(5), Times::from(10));
+        assert_eq!(Times::from(u64::MAX) + 1, Times::from(u64::MAX));
+    }
+
+    #[test]
+    fn sub() {
+        assert_eq!(Times::from(105) - 100, Times::from(5));
+        assert_eq!(Times::from(5) - Times::from(10), Times::from(0));
+    }
+
+    #[test]
+    fn mul() {
+        assert_eq!(Times::from(5) * 2, Times::from(10));
+        assert_eq!(Times::from(u64::MAX) * 2, Times::from(u64::MAX));
+    }
+
+    #[test]
+    fn div() {
+        assert_eq!(Times::from(10) / 3, Times::from(3));
+        assert_eq!(Times::from(u64::MAX) / 1, Times::from(u64::MAX));
+    }
+
+    #[test]
+    fn checked_add() {
+        assert_eq!(Times::from(5).checked_add(100), Some(Times::from(105)));
+        assert_eq!(Times::from(u64::MAX).checked_add(1), None);
+    }
+
+    #[test]
+    fn checked_mul() {
+        assert_eq!(Times::from(5).checked_mul(2), Some(Times::from(10)));
+        assert_eq!(Times::from(u64::MAX).checked_mul(2), None);
+    }
+
+    #[test]
+    fn measure_time_zero_times() {
+        assert_eq!(measure_time(sleep, Times(0)), Duration::ZERO);
+        assert_eq!(measure_time_with_warmup(sleep, Times(1), Times(0)), Duration::ZERO);
+        assert_eq!(measure_time_setup(|| (), |_| sleep(), Times(0)), Duration::ZERO);
+    }
+
+    #[test]
+    fn sweep() {
+        assert_eq!(Times::sweep(Times(10), Times(40), 10).collect::<Vec<_>>(), [Times(10), Times(20), Times(30), Times(40)]);
+        assert_eq!(Times::sweep(Times(1), Times(6), 2).collect::<Vec<_>>(), [Times(1), Times(3), Times(5)]);
+        assert_eq!(Times::sweep(Times(5), Times(1), 1).count(), 0);
+    }
+
+    #[test]
+    fn measure_sweep_pairs_counts_with_durations() {
+        let mut calls = 0;
+        let results = measure_sweep(|| calls += 1, Times::sweep(Times(10), Times(30), 10));
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.iter().map(|(times, _)| *times).collect::<Vec<_>>(), [Times(10), Times(20), Times(30)]);
+        assert_eq!(calls, 60);
+    }
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        let times: Times = "5000".parse().unwrap();
+
+        assert_eq!(times, Times::from(5000));
+        assert_eq!(times.to_string(), "5000");
+        assert_eq!(times.to_string().parse::<Times>(), Ok(times));
+    }
+
+    #[test]
+    fn parse_failure() {
+        assert!(matches!("many".parse::<Times>(), Err(ParseTimesError::Invalid(_))));
+        assert_eq!("-5".parse::<Times>(), Err(ParseTimesError::Negative));
+        assert_eq!(ParseTimesError::Negative.to_string(), "number of times can't be negative");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_as_plain_integer() {
+        let times: Times = serde_json::from_str("5000").unwrap();
+
+        assert_eq!(times, Times::from(5000));
+        assert_eq!(serde_json::to_string(&times).unwrap(), "5000");
+        assert!(serde_json::from_str::<Times>("-5").is_err());
+        assert!(serde_json::from_str::<Times>("5.5").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn timing_stats_json_round_trip() {
+        let stats = TimingStats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(20), Duration::from_nanos(30)]);
+        let json = stats.to_json();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["min_ns"], 10);
+        assert_eq!(value["max_ns"], 30);
+        assert_eq!(value["mean_ns"], 20);
+        assert_eq!(value["p50_ns"], 20);
+        assert_eq!(value["std_dev_ns"], 8);
+        assert_eq!(serde_json::from_str::<TimingStats>(&json).unwrap(), stats);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn write_labeled_report() {
+        let path = std::env::temp_dir().join(format!("bevy_hectic_utils_report_{}.json", std::process::id()));
+        let fast = TimingStats::from_samples(&[Duration::from_nanos(5)]);
+        let slow = TimingStats::from_samples(&[Duration::from_micros(5)]);
+
+        write_report(&path, &[("fast", fast), ("slow", slow)]).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(report[0]["label"], "fast");
+        assert_eq!(report[0]["mean_ns"], 5);
+        assert_eq!(report[1]["label"], "slow");
+        assert_eq!(report[1]["p50_ns"], 5000);
+        assert_eq!(report.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn mean_keeps_sub_nanosecond_precision() {
+        assert_eq!(mean_nanos(Duration::from_nanos(3), Times::from(4)), 0.75);
+        assert_eq!(mean_nanos(Duration::from_nanos(3), Times::from(0)), 0.0);
+        assert_eq!(mean(Duration::from_nanos(7), Times::from(2)), Duration::from_nanos(4));
+    }
+
+    #[test]
+    fn cheap_closure_has_nonzero_mean() {
+        let mut counter = 0u64;
+        let times = Times::from(100_000);
+
+        assert!(measure_time_nanos(|| counter = black_box(counter).wrapping_add(1), times) > 0.0);
+        assert!(measure_time(|| counter = black_box(counter).wrapping_add(1), times) > Duration::ZERO);
+    }
+
+    #[test]
+    fn profiler_labeled_scopes() {
+        let mut profiler = Profiler::new();
+        {
+            let mut load = profiler.scope("load");
+            std::thread::sleep(Duration::from_millis(5));
+            let _parse = load.scope("parse");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        profiler.start("render");
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(profiler.stop("render").unwrap() >= Duration::from_millis(5));
+        assert_eq!(profiler.stop("render"), None);
+
+        let report = profiler.report();
+        assert_eq!(report.len(), 3);
+        assert!(report["parse"] >= Duration::from_millis(10));
+        assert!(report["load"] >= report["parse"] + Duration::from_millis(5));
+        assert!(report["load"] < Duration::from_secs(5));
+        assert!(report["render"] >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn profiler_accumulates_same_label() {
+        let mut profiler = Profiler::new();
+        for _ in 0..3 {
+            let _step = profiler.scope("step");
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        profiler.start("unfinished");
+
+        let report = profiler.report();
+        assert!(report["step"] >= Duration::from_millis(6));
+        assert!(!report.contains_key("unfinished"));
+    }
+
+    #[test]
+    fn times_from_env() {
+        const VAR: &str = "BEVY_HECTIC_UTILS_TEST_BENCH_TIMES";
+        let _env = lock_env();
+        let _var = EnvVar::set(VAR, None);
+        let default = Times::from(10);
+
+        assert_eq!(Times::from_env(VAR, default), default);
+
+        std::env::set_var(VAR, "1000000");
+        assert_eq!(Times::from_env(VAR, default), Times::from(1_000_000));
+
+        for invalid in ["", "many", "-5", "1e6"] {
+            std::env::set_var(VAR, invalid);
+            assert_eq!(Times::from_env(VAR, default), default);
+        }
+    }
+
+    #[test]
+    fn measure_time_catching_panic() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = measure_time_catching(
+            || {
+                let call = calls.fetch_add(1, Ordering::Relaxed) + 1;
+                assert!(call != 5, "5th call fails");
+            },
+            Times::from(10),
+        );
+
+        assert_eq!(result, Err(5));
+        assert_eq!(calls.load(Ordering::Relaxed), 5);
+        assert!(measure_time_catching(|| black_box(1) + 1, Times::from(10)).is_ok());
+    }
+
+    #[test]
+    fn measure_parallel_runs_every_thread() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let calls = AtomicU64::new(0);
+        for threads in [2, 4] {
+            calls.store(0, Ordering::Relaxed);
+            let elapsed = measure_parallel(|| calls.fetch_add(1, Ordering::Relaxed), threads, Times::from(1000));
+
+            assert!(elapsed > Duration::ZERO);
+            assert_eq!(calls.load(Ordering::Relaxed), threads as u64 * 1000);
+        }
+    }
+
+    #[test]
+    fn default_for_profile() {
+        if cfg!(debug_assertions) {
+            assert_eq!(Times::default_for_profile(), Times::from(1000));
+            assert!(Times::default_for_profile().get() < Times::default().get());
+        } else {
+            assert_eq!(Times::default_for_profile(), Times::default());
+        }
+    }
+
+    #[test]
+    fn coefficient_of_variation() {
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_micros);
+        let stats = TimingStats::from_samples(&samples);
+
+        // mean 5µs, population standard deviation 2µs
+        assert_eq!(stats.std_dev, Duration::from_micros(2));
+        assert!((stats.cv() - 0.4).abs() < 1e-9);
+        assert_eq!(TimingStats::from_samples(&[Duration::from_nanos(7); 4]).cv(), 0.0);
+        assert_eq!(TimingStats::from_samples(&[]).cv(), 0.0);
+    }
+}
@@ -0,0 +1,525 @@
+use std::{borrow::Cow, hash::Hash, ops::Deref};
+#[cfg(feature = "strum")]
+use std::{any::type_name, fmt::Debug};
+
+use bevy_app::{App, Plugin, Update};
+#[cfg(feature = "strum")]
+use bevy_derive::{Deref, DerefMut};
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic};
+#[cfg(feature = "strum")]
+use bevy_ecs::schedule::{IntoSystemConfigs, ScheduleLabel, SystemConfigs, SystemSet};
+use bevy_ecs::system::{Res, Resource};
+use bevy_utils::hashbrown::HashMap;
+#[cfg(feature = "strum")]
+use strum::IntoEnumIterator;
+
+use crate::bench::timed;
+#[cfg(feature = "strum")]
+use crate::{check_hash_map, missing_enum_variants, EnumIndex, EnumMap};
+
+/// Creates a system validating that the map stored in the resource `R` contains exactly one of each
+/// possible key, see [`validate_hash_map`](crate::validate_hash_map)
+///
+/// Meant to run at [`Startup`](bevy_app::Startup) so that an incomplete dispatch table fails fast.
+///
+/// # Panics
+///
+/// The system panics, naming the resource and the missing keys, if the map is incomplete.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::{App, Startup};
+/// use bevy_derive::Deref;
+/// use bevy_ecs::system::Resource;
+/// use bevy_hectic_utils::{bevy::validate_resource_map, build_enum_map};
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum State {
+///     Menu,
+///     Game,
+/// }
+///
+/// #[derive(Resource, Deref)]
+/// struct Scenes(HashMap<State, &'static str>);
+///
+/// App::new()
+///     .insert_resource(Scenes(build_enum_map(|state| match state {
+///         State::Menu => "menu.scn",
+///         State::Game => "game.scn",
+///     })))
+///     .add_systems(Startup, validate_resource_map::<Scenes, State, &str>())
+///     .update();
+/// ```
+#[cfg(feature = "strum")]
+pub fn validate_resource_map<R, K, V>() -> impl Fn(Res<R>)
+where
+    R: Resource + Deref<Target = HashMap<K, V>>,
+    K: Eq + Hash + Debug + IntoEnumIterator,
+{
+    |resource: Res<R>| {
+        if let Err(error) = check_hash_map(&**resource) {
+            panic!("{}: {error}", type_name::<R>());
+        }
+    }
+}
+
+/// [`EnumMap`] stored as a resource, e.g. a handler for every state
+///
+/// Pair with [`validate_enum_resource`] to make sure every key has a value.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::{App, Startup};
+/// use bevy_hectic_utils::{bevy::{validate_enum_resource, EnumResource}, impl_enum_index, EnumMap};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+/// enum State {
+///     Menu,
+///     Game,
+/// }
+///
+/// impl_enum_index!(State);
+///
+/// let mut scenes = EnumMap::new();
+/// scenes.insert(State::Menu, "menu.scn");
+/// scenes.insert(State::Game, "game.scn");
+///
+/// App::new()
+///     .insert_resource(EnumResource(scenes))
+///     .add_systems(Startup, validate_enum_resource::<State, &str>())
+///     .update();
+/// ```
+#[cfg(feature = "strum")]
+#[derive(Resource, Deref, DerefMut, Debug, Clone, PartialEq, Eq)]
+pub struct EnumResource<K, V>(pub EnumMap<K, V>)
+where
+    K: EnumIndex;
+
+/// Creates a system validating that the [`EnumResource`] holds a value for every key
+///
+/// Meant to run at [`Startup`](bevy_app::Startup), like [`validate_resource_map`].
+///
+/// # Panics
+///
+/// The system panics, naming the resource and the missing keys, if a key has no value.
+#[cfg(feature = "strum")]
+pub fn validate_enum_resource<K, V>() -> impl Fn(Res<EnumResource<K, V>>)
+where
+    K: EnumIndex + PartialEq + Debug + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    |resource: Res<EnumResource<K, V>>| {
+        let missing = missing_enum_variants::<K, _>(resource.iter().map(|(key, _)| key));
+        if !missing.is_empty() {
+            panic!("{}: enum map is missing keys: {missing:?}", type_name::<EnumResource<K, V>>());
+        }
+    }
+}
+
+/// Adds the systems of a map to `schedule`, chained in [`IntoEnumIterator::iter`] order of their keys
+///
+/// Lets systems be declared per phase of an enum, with the enum declaration defining the order they run in.
+/// Besides the app and the map, takes the schedule to add all of the systems to, e.g. [`Update`].
+///
+/// # Panics
+///
+/// Panics, naming the enum and the missing keys, if the map doesn't contain exactly one entry for every
+/// key, see [`check_hash_map`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::{App, Update};
+/// use bevy_ecs::schedule::IntoSystemConfigs;
+/// use bevy_hectic_utils::bevy::add_enum_ordered_systems;
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Phase {
+///     Input,
+///     Physics,
+///     Render,
+/// }
+///
+/// fn read_input() {}
+/// fn simulate() {}
+/// fn draw() {}
+///
+/// let mut systems = HashMap::new();
+/// systems.insert(Phase::Render, draw.into_configs());
+/// systems.insert(Phase::Input, read_input.into_configs());
+/// systems.insert(Phase::Physics, simulate.into_configs());
+///
+/// let mut app = App::new();
+/// add_enum_ordered_systems(&mut app, Update, systems);
+/// app.update();
+/// ```
+#[cfg(feature = "strum")]
+pub fn add_enum_ordered_systems<P>(app: &mut App, schedule: impl ScheduleLabel + Clone, mut map: HashMap<P, SystemConfigs>) -> &mut App
+where
+    P: Clone + Eq + Hash + Debug + Send + Sync + 'static + IntoEnumIterator,
+{
+    if let Err(error) = check_hash_map(&map) {
+        panic!("{}: {error}", type_name::<P>());
+    }
+    let mut previous = None;
+    for phase in P::iter() {
+        let Some(configs) = map.remove(&phase) else {
+            continue;
+        };
+        let configs = configs.in_set(PhaseSet(phase.clone()));
+        let configs = match previous.replace(PhaseSet(phase)) {
+            Some(previous) => configs.after(previous),
+            None => configs,
+        };
+        app.add_systems(schedule.clone(), configs);
+    }
+    app
+}
+
+/// Systems of a phase added by [`add_enum_ordered_systems`], each phase runs after the one before it
+#[cfg(feature = "strum")]
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct PhaseSet<P: Clone + Eq + Hash + Debug + Send + Sync + 'static>(P);
+
+/// Creates a run condition that is `true` when `state` is flagged as enabled in the map stored in the
+/// resource `R`
+///
+/// A state without an entry counts as disabled, pair with
+/// [`validate_resource_map`] to make sure every state has one.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::{App, Update};
+/// use bevy_derive::Deref;
+/// use bevy_ecs::{schedule::IntoSystemConfigs, system::Resource};
+/// use bevy_hectic_utils::bevy::run_if_state_enabled;
+/// use bevy_utils::HashMap;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum State {
+///     Menu,
+///     Game,
+/// }
+///
+/// #[derive(Resource, Deref)]
+/// struct Physics(HashMap<State, bool>);
+///
+/// fn simulate() {}
+///
+/// App::new()
+///     .insert_resource(Physics(HashMap::from([(State::Menu, false), (State::Game, true)])))
+///     .add_systems(Update, simulate.run_if(run_if_state_enabled::<Physics, _>(State::Game)))
+///     .update();
+/// ```
+pub fn run_if_state_enabled<R, S>(state: S) -> impl Fn(Res<R>) -> bool + Clone
+where
+    R: Resource + Deref<Target = HashMap<S, bool>>,
+    S: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    move |resource: Res<R>| resource.get(&state).copied().unwrap_or(false)
+}
+
+/// Plugin measuring a closure every frame and recording its duration in milliseconds as a Bevy
+/// [`Diagnostic`]
+///
+/// Like other diagnostics, the closure is only called while the diagnostic is enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::App;
+/// use bevy_diagnostic::{DiagnosticId, DiagnosticsStore};
+/// use bevy_hectic_utils::bevy::HecticTimingPlugin;
+///
+/// const PATHFINDING: DiagnosticId = DiagnosticId::from_u128(0x7a1d_5e4c);
+///
+/// let mut app = App::new();
+/// app.add_plugins(HecticTimingPlugin::new(PATHFINDING, "pathfinding", || {
+///     (0..1000u64).sum::<u64>();
+/// }));
+/// app.update();
+///
+/// let diagnostics = app.world.resource::<DiagnosticsStore>();
+/// assert!(diagnostics.get(PATHFINDING).unwrap().value().is_some());
+/// ```
+pub struct HecticTimingPlugin<F> {
+    id: DiagnosticId,
+    name: Cow<'static, str>,
+    max_history_length: usize,
+    measured: F,
+}
+
+impl<F> HecticTimingPlugin<F>
+where
+    F: Fn() + Clone + Send + Sync + 'static,
+{
+    const DEFAULT_MAX_HISTORY_LENGTH: usize = 20;
+
+    /// Measure `measured` under the diagnostic `id` named `name`
+    pub fn new(id: DiagnosticId, name: impl Into<Cow<'static, str>>, measured: F) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            max_history_length: Self::DEFAULT_MAX_HISTORY_LENGTH,
+            measured,
+        }
+    }
+
+    /// Keep at most `max_history_length` measurements, 20 by default
+    pub fn with_max_history_length(mut self, max_history_length: usize) -> Self {
+        self.max_history_length = max_history_length;
+        self
+    }
+}
+
+impl<F> Plugin for HecticTimingPlugin<F>
+where
+    F: Fn() + Clone + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        let id = self.id;
+        let measured = self.measured.clone();
+        app.register_diagnostic(Diagnostic::new(id, self.name.clone(), self.max_history_length).with_suffix("ms"))
+            .add_systems(Update, move |mut diagnostics: Diagnostics| {
+                diagnostics.add_measurement(id, || timed(&measured).1.as_secs_f64() * 1000.0);
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "strum")]
+    mod enum_resource {
+        use bevy_app::{App, Startup};
+        use strum::EnumIter;
+
+        use crate::{
+            bevy::{validate_enum_resource, EnumResource},
+            EnumMap,
+        };
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+        enum State {
+            Menu,
+            Game,
+        }
+
+        crate::impl_enum_index!(State);
+
+        #[test]
+        fn insert_and_read_back() {
+            let mut scenes = EnumMap::new();
+            scenes.insert(State::Menu, "menu.scn");
+            scenes.insert(State::Game, "game.scn");
+
+            let mut app = App::new();
+            app.insert_resource(EnumResource(scenes.clone()))
+                .add_systems(Startup, validate_enum_resource::<State, &str>());
+            app.update();
+
+            app.world.resource_mut::<EnumResource<State, &str>>().insert(State::Game, "level_1.scn");
+            let resource = app.world.resource::<EnumResource<State, &str>>();
+            assert_eq!(resource.get(State::Menu), Some(&"menu.scn"));
+            assert_eq!(resource.get(State::Game), Some(&"level_1.scn"));
+        }
+
+        #[test]
+        #[should_panic(expected = "enum map is missing keys: [Game]")]
+        fn validate_incomplete() {
+            let mut scenes = EnumMap::new();
+            scenes.insert(State::Menu, "menu.scn");
+
+            App::new()
+                .insert_resource(EnumResource(scenes))
+                .add_systems(Startup, validate_enum_resource::<State, &str>())
+                .update();
+        }
+    }
+
+    mod hectic_timing_plugin {
+        use std::{thread, time::Duration};
+
+        use bevy_app::App;
+        use bevy_diagnostic::{DiagnosticId, DiagnosticsStore};
+
+        use crate::bevy::HecticTimingPlugin;
+
+        const SLEEP: DiagnosticId = DiagnosticId::from_u128(1);
+
+        #[test]
+        fn records_measurements() {
+            let mut app = App::new();
+            app.add_plugins(HecticTimingPlugin::new(SLEEP, "sleep", || thread::sleep(Duration::from_millis(2))));
+            for _ in 0..3 {
+                app.update();
+            }
+
+            let diagnostic = app.world.resource::<DiagnosticsStore>().get(SLEEP).unwrap();
+            assert_eq!(diagnostic.history_len(), 3);
+            assert!(diagnostic.values().all(|value| *value >= 2.0));
+        }
+
+        #[test]
+        fn disabled_diagnostic_skips_closure() {
+            let mut app = App::new();
+            app.add_plugins(HecticTimingPlugin::new(SLEEP, "sleep", || panic!("measured while disabled")));
+            app.world.resource_mut::<DiagnosticsStore>().get_mut(SLEEP).unwrap().is_enabled = false;
+            app.update();
+
+            assert_eq!(app.world.resource::<DiagnosticsStore>().get(SLEEP).unwrap().history_len(), 0);
+        }
+    }
+
+    #[cfg(feature = "strum")]
+    mod add_enum_ordered_systems {
+        use bevy_app::{App, Update};
+        use bevy_ecs::{
+            schedule::IntoSystemConfigs,
+            system::{ResMut, Resource},
+        };
+        use bevy_utils::HashMap;
+        use strum::EnumIter;
+
+        use crate::bevy::add_enum_ordered_systems;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+        enum Phase {
+            First,
+            Second,
+            Third,
+        }
+
+        #[derive(Resource, Default)]
+        struct Runs(Vec<Phase>);
+
+        fn run(phase: Phase) -> impl Fn(ResMut<Runs>) {
+            move |mut runs: ResMut<Runs>| runs.0.push(phase)
+        }
+
+        #[test]
+        fn systems_run_in_variant_order() {
+            let mut map = HashMap::new();
+            map.insert(Phase::Third, run(Phase::Third).into_configs());
+            map.insert(Phase::First, run(Phase::First).into_configs());
+            map.insert(Phase::Second, run(Phase::Second).into_configs());
+
+            let mut app = App::new();
+            app.init_resource::<Runs>();
+            add_enum_ordered_systems(&mut app, Update, map);
+            app.update();
+
+            assert_eq!(app.world.resource::<Runs>().0, [Phase::First, Phase::Second, Phase::Third]);
+        }
+
+        #[test]
+        #[should_panic(expected = "hash map is missing keys: [Second]")]
+        fn incomplete_map() {
+            let mut map = HashMap::new();
+            map.insert(Phase::First, run(Phase::First).into_configs());
+            map.insert(Phase::Third, run(Phase::Third).into_configs());
+
+            add_enum_ordered_systems(&mut App::new(), Update, map);
+        }
+    }
+
+    mod run_if_state_enabled {
+        use bevy_app::{App, Update};
+        use bevy_derive::Deref;
+        use bevy_ecs::{
+            schedule::IntoSystemConfigs,
+            system::{ResMut, Resource},
+        };
+        use bevy_utils::HashMap;
+
+        use crate::bevy::run_if_state_enabled;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum State {
+            Menu,
+            Game,
+        }
+
+        #[derive(Resource, Deref)]
+        struct Flags(HashMap<State, bool>);
+
+        #[derive(Resource, Default)]
+        struct Runs(u32);
+
+        fn count(mut runs: ResMut<Runs>) {
+            runs.0 += 1;
+        }
+
+        fn runs(state: State, flags: HashMap<State, bool>) -> u32 {
+            let mut app = App::new();
+            app.insert_resource(Flags(flags))
+                .init_resource::<Runs>()
+                .add_systems(Update, count.run_if(run_if_state_enabled::<Flags, _>(state)));
+            app.update();
+            app.world.resource::<Runs>().0
+        }
+
+        #[test]
+        fn enabled_state() {
+            assert_eq!(runs(State::Game, HashMap::from([(State::Menu, false), (State::Game, true)])), 1);
+        }
+
+        #[test]
+        fn disabled_state() {
+            assert_eq!(runs(State::Menu, HashMap::from([(State::Menu, false), (State::Game, true)])), 0);
+        }
+
+        #[test]
+        fn missing_state() {
+            assert_eq!(runs(State::Game, HashMap::from([(State::Menu, true)])), 0);
+        }
+    }
+
+    #[cfg(feature = "strum")]
+    mod validate_resource_map {
+        use bevy_app::{App, Startup};
+        use bevy_derive::Deref;
+        use bevy_ecs::system::Resource;
+        use bevy_utils::HashMap;
+        use strum::EnumIter;
+
+        use crate::{bevy::validate_resource_map, build_enum_map};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+        enum Level {
+            First,
+            Second,
+        }
+
+        #[derive(Resource, Deref)]
+        struct Handlers(HashMap<Level, i32>);
+
+        fn app(handlers: Handlers) -> App {
+            let mut app = App::new();
+            app.insert_resource(handlers)
+                .add_systems(Startup, validate_resource_map::<Handlers, Level, i32>());
+            app
+        }
+
+        #[test]
+        fn complete_resource() {
+            app(Handlers(build_enum_map(|level| level as i32))).update();
+        }
+
+        #[test]
+        #[should_panic(expected = "hash map is missing keys: [Second]")]
+        fn incomplete_resource() {
+            let mut map = HashMap::new();
+            map.insert(Level::First, 1);
+
+            app(Handlers(map)).update();
+        }
+    }
+}
@@ -0,0 +1,656 @@
+use std::{
+    fmt,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use bevy_utils::hashbrown::HashMap;
+use strum::IntoEnumIterator;
+
+/// Position of a variant in [`IntoEnumIterator::iter`] order, the slot [`EnumMap`] and [`EnumIndexed`] store
+/// its value in.
+///
+/// It makes lookups O(1): [`impl_enum_index!`](crate::impl_enum_index!) implements it for fieldless enums by
+/// casting variants to `usize`. Implement it by hand for enums with explicit discriminants or
+/// `#[strum(disabled)]` variants.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::EnumIndex;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+/// enum Priority {
+///     Low = 10,
+///     High = 20,
+/// }
+///
+/// impl EnumIndex for Priority {
+///     fn enum_index(&self) -> Option<usize> {
+///         Some(match self {
+///             Priority::Low => 0,
+///             Priority::High => 1,
+///         })
+///     }
+/// }
+/// ```
+pub trait EnumIndex: IntoEnumIterator {
+    /// Position of `self` in [`IntoEnumIterator::iter`] order, [`None`] if it isn't yielded by it.
+    fn enum_index(&self) -> Option<usize>;
+}
+
+/// Implements [`EnumIndex`](crate::EnumIndex) for fieldless enums by casting variants to `usize`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{impl_enum_index, EnumIndex};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+/// enum Side {
+///     Left,
+///     Right,
+/// }
+///
+/// impl_enum_index!(Side);
+///
+/// assert_eq!(Side::Right.enum_index(), Some(1));
+/// ```
+///
+/// # Notes
+///
+/// - The enum must be [`Copy`].
+/// - The cast matches [`IntoEnumIterator::iter`](strum::IntoEnumIterator::iter) order only for enums without
+///   explicit discriminants or `#[strum(disabled)]` variants, which is checked whenever a map is created.
+///
+#[macro_export]
+macro_rules! impl_enum_index {
+    ($( $enum: ty ),+ $(,)?) => {
+        $(
+            impl $crate::EnumIndex for $enum {
+                fn enum_index(&self) -> ::core::option::Option<usize> {
+                    ::core::option::Option::Some(*self as usize)
+                }
+            }
+        )+
+    };
+}
+
+/// Checks that [`EnumIndex::enum_index`] agrees with [`IntoEnumIterator::iter`] order, in release builds too, since
+/// a disagreeing index would silently permute the entries.
+fn check_enum_index<K: EnumIndex>() {
+    for (position, key) in K::iter().enumerate() {
+        assert_eq!(key.enum_index(), Some(position), "`EnumIndex` disagrees with `IntoEnumIterator::iter` order");
+    }
+}
+
+/// A map keyed by an enum, storing its values in a boxed slice instead of a hash table.
+///
+/// Every key owns the slot at its position in [`IntoEnumIterator::iter`] order, given by [`EnumIndex`], so
+/// lookups never hash or search anything. It targets the same use case as [`validate_hash_map`](crate::validate_hash_map):
+/// small enums that need a value per variant.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{impl_enum_index, EnumMap};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum LevelState {
+///     Level1,
+///     Level2,
+/// }
+///
+/// impl_enum_index!(LevelState);
+///
+/// let mut scenes = EnumMap::new();
+/// scenes.insert(LevelState::Level1, "level_1.scn");
+///
+/// assert_eq!(scenes.get(LevelState::Level1), Some(&"level_1.scn"));
+/// assert_eq!(scenes.get(LevelState::Level2), None);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct EnumMap<K, V> {
+    values: Box<[Option<V>]>,
+    marker: PhantomData<K>,
+}
+
+impl<K, V> EnumMap<K, V>
+where
+    K: EnumIndex,
+{
+    /// Creates an empty map with a slot for every key of `K`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`EnumIndex`] disagrees with [`IntoEnumIterator::iter`] order.
+    pub fn new() -> Self {
+        check_enum_index::<K>();
+        Self {
+            values: K::iter().map(|_| None).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the value of the key, if any.
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.values.get(key.enum_index()?)?.as_ref()
+    }
+
+    /// Inserts a value for the key, returning the previous value, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not yielded by [`IntoEnumIterator::iter`] (e.g. a `#[strum(disabled)]` variant).
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.slot_mut(&key)
+            .expect("key is not yielded by `IntoEnumIterator::iter`")
+            .replace(value)
+    }
+
+    /// Iterates over the present entries in [`IntoEnumIterator::iter`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        K::iter()
+            .zip(self.values.iter())
+            .filter_map(|(key, value)| Some((key, value.as_ref()?)))
+    }
+
+    /// Iterates mutably over the present entries in [`IntoEnumIterator::iter`] order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        K::iter()
+            .zip(self.values.iter_mut())
+            .filter_map(|(key, value)| Some((key, value.as_mut()?)))
+    }
+
+    fn slot_mut(&mut self, key: &K) -> Option<&mut Option<V>> {
+        self.values.get_mut(key.enum_index()?)
+    }
+}
+
+impl<K, V> Default for EnumMap<K, V>
+where
+    K: EnumIndex,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for EnumMap<K, V>
+where
+    K: EnumIndex + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> EnumMap<K, V>
+where
+    K: EnumIndex + Eq + Hash,
+{
+    /// Creates a map with a value for every key, taken from `hash_map` or produced by `default` if it lacks the key.
+    ///
+    /// Keys not yielded by [`IntoEnumIterator::iter`] (e.g. `#[strum(disabled)]` variants) are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`EnumIndex`] disagrees with [`IntoEnumIterator::iter`] order.
+    pub fn from_hashmap_or_default(mut hash_map: HashMap<K, V>, default: impl Fn(K) -> V) -> Self {
+        check_enum_index::<K>();
+        Self {
+            values: K::iter()
+                .map(|key| Some(hash_map.remove(&key).unwrap_or_else(|| default(key))))
+                .collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Copies the present entries into a [`HashMap`](bevy_utils::HashMap).
+    ///
+    /// If every key has a value (e.g. the map was built by [`EnumMap::from_hashmap_or_default`]), the result
+    /// passes [`validate_hash_map`](crate::validate_hash_map).
+    pub fn to_hashmap(&self) -> HashMap<K, V>
+    where
+        V: Clone,
+    {
+        self.iter().map(|(key, value)| (key, value.clone())).collect()
+    }
+
+    /// Copies the present entries into a [`std::collections::HashMap`], see [`EnumMap::to_hashmap`].
+    pub fn to_std_hashmap(&self) -> std::collections::HashMap<K, V>
+    where
+        V: Clone,
+    {
+        self.iter().map(|(key, value)| (key, value.clone())).collect()
+    }
+}
+
+impl<K, V> IntoIterator for EnumMap<K, V>
+where
+    K: IntoEnumIterator,
+{
+    type Item = (K, V);
+    type IntoIter = std::iter::FilterMap<
+        std::iter::Zip<K::Iterator, std::vec::IntoIter<Option<V>>>,
+        fn((K, Option<V>)) -> Option<(K, V)>,
+    >;
+
+    /// Consumes the map into its present entries in [`IntoEnumIterator::iter`] order.
+    fn into_iter(self) -> Self::IntoIter {
+        K::iter()
+            .zip(self.values.into_vec())
+            .filter_map(|(key, value)| Some((key, value?)))
+    }
+}
+
+/// Keys not yielded by [`IntoEnumIterator::iter`] (e.g. `#[strum(disabled)]` variants) are dropped.
+impl<K, V> From<HashMap<K, V>> for EnumMap<K, V>
+where
+    K: EnumIndex,
+{
+    fn from(hash_map: HashMap<K, V>) -> Self {
+        let mut map = Self::new();
+        for (key, value) in hash_map {
+            if let Some(slot) = map.slot_mut(&key) {
+                *slot = Some(value);
+            }
+        }
+        map
+    }
+}
+
+/// Serializes as a map from variant names, given by [`AsRef<str>`] (e.g. derived with [`strum::AsRefStr`]), to
+/// values of the present keys.
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for EnumMap<K, V>
+where
+    K: EnumIndex + AsRef<str>,
+    V: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.iter().count()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key.as_ref(), value)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes from a map from variant names to values, failing on unknown, duplicated or missing variants.
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for EnumMap<K, V>
+where
+    K: EnumIndex + AsRef<str>,
+    V: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for Visitor<K, V>
+        where
+            K: EnumIndex + AsRef<str>,
+            V: serde::Deserialize<'de>,
+        {
+            type Value = EnumMap<K, V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from variant names to values")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                use serde::de::Error;
+
+                let mut map = EnumMap::new();
+                while let Some(name) = access.next_key::<String>()? {
+                    let key = K::iter()
+                        .find(|key| key.as_ref() == name)
+                        .ok_or_else(|| A::Error::custom(format_args!("unknown variant `{name}`")))?;
+                    if map.insert(key, access.next_value()?).is_some() {
+                        return Err(A::Error::custom(format_args!("duplicate variant `{name}`")));
+                    }
+                }
+
+                match K::iter().zip(map.values.iter()).find(|(_, value)| value.is_none()) {
+                    Some((key, _)) => Err(A::Error::custom(format_args!("missing variant `{}`", key.as_ref()))),
+                    None => Ok(map),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(Visitor(PhantomData))
+    }
+}
+
+/// A value for every key of an enum, indexable with `map[key]`.
+///
+/// Unlike [`EnumMap`], it is built with [`EnumIndexed::from_fn`], so every key has a value and indexing
+/// never misses.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{impl_enum_index, EnumIndexed};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+/// enum Resource {
+///     Wood,
+///     Stone,
+/// }
+///
+/// impl_enum_index!(Resource);
+///
+/// let mut stock = EnumIndexed::from_fn(|_: Resource| 0);
+/// stock[Resource::Wood] += 10;
+///
+/// assert_eq!(stock[Resource::Wood], 10);
+/// assert_eq!(stock[Resource::Stone], 0);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct EnumIndexed<K, V> {
+    values: Box<[V]>,
+    marker: PhantomData<K>,
+}
+
+impl<K, V> EnumIndexed<K, V>
+where
+    K: EnumIndex,
+{
+    /// Creates a map holding `f(key)` for every key of `K`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`EnumIndex`] disagrees with [`IntoEnumIterator::iter`] order.
+    pub fn from_fn(f: impl FnMut(K) -> V) -> Self {
+        check_enum_index::<K>();
+        Self {
+            values: K::iter().map(f).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates over the entries in [`IntoEnumIterator::iter`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        K::iter().zip(self.values.iter())
+    }
+
+    fn index_of(&self, key: &K) -> usize {
+        key.enum_index()
+            .filter(|&index| index < self.values.len())
+            .expect("key is not yielded by `IntoEnumIterator::iter`")
+    }
+}
+
+impl<K, V> Index<K> for EnumIndexed<K, V>
+where
+    K: EnumIndex,
+{
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// Panics if the key is not yielded by [`IntoEnumIterator::iter`] (e.g. a `#[strum(disabled)]` variant).
+    fn index(&self, key: K) -> &V {
+        &self.values[self.index_of(&key)]
+    }
+}
+
+impl<K, V> IndexMut<K> for EnumIndexed<K, V>
+where
+    K: EnumIndex,
+{
+    /// # Panics
+    ///
+    /// Panics if the key is not yielded by [`IntoEnumIterator::iter`] (e.g. a `#[strum(disabled)]` variant).
+    fn index_mut(&mut self, key: K) -> &mut V {
+        let index = self.index_of(&key);
+        &mut self.values[index]
+    }
+}
+
+impl<K, V> fmt::Debug for EnumIndexed<K, V>
+where
+    K: EnumIndex + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_utils::HashMap;
+    use strum::{AsRefStr, EnumIter, IntoEnumIterator};
+
+    use super::{EnumIndex, EnumIndexed, EnumMap};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, AsRefStr)]
+    enum Level {
+        First,
+        Second,
+        Third,
+    }
+
+    crate::impl_enum_index!(Level);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+    enum Toggle {
+        On,
+        #[strum(disabled)]
+        Off,
+        Auto,
+    }
+
+    impl EnumIndex for Toggle {
+        fn enum_index(&self) -> Option<usize> {
+            match self {
+                Toggle::On => Some(0),
+                Toggle::Off => None,
+                Toggle::Auto => Some(1),
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut map = EnumMap::new();
+        assert_eq!(map.insert(Level::Second, "second"), None);
+        assert_eq!(map.insert(Level::Second, "2nd"), Some("second"));
+
+        assert_eq!(map.get(Level::First), None);
+        assert_eq!(map.get(Level::Second), Some(&"2nd"));
+    }
+
+    #[test]
+    fn matches_equivalent_hash_map() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert(Level::First, 1);
+        hash_map.insert(Level::Third, 3);
+
+        let map = EnumMap::from(hash_map.clone());
+
+        for level in [Level::First, Level::Second, Level::Third] {
+            assert_eq!(map.get(level), hash_map.get(&level));
+        }
+        assert_eq!(map.iter().count(), hash_map.len());
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Level::First, &1), (Level::Third, &3)]);
+    }
+
+    #[test]
+    fn from_complete_hashmap_or_default() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert(Level::First, 1);
+        hash_map.insert(Level::Second, 2);
+        hash_map.insert(Level::Third, 3);
+
+        let map = EnumMap::from_hashmap_or_default(hash_map, |_| unreachable!());
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Level::First, &1), (Level::Second, &2), (Level::Third, &3)]);
+    }
+
+    #[test]
+    fn from_partial_hashmap_or_default() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert(Level::Second, 2);
+
+        let map = EnumMap::from_hashmap_or_default(hash_map, |level| level as i32 * 10);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Level::First, &0), (Level::Second, &2), (Level::Third, &20)]);
+    }
+
+    #[test]
+    fn index_each_variant() {
+        let map = EnumIndexed::from_fn(|level| level as usize * 10);
+
+        assert_eq!(map[Level::First], 0);
+        assert_eq!(map[Level::Second], 10);
+        assert_eq!(map[Level::Third], 20);
+        assert_eq!(map.iter().count(), 3);
+    }
+
+    #[test]
+    fn mutate_through_index_mut() {
+        let mut map = EnumIndexed::from_fn(|_: Level| Vec::new());
+        map[Level::Second].push("second");
+        map[Level::Second].push("2nd");
+
+        assert!(map[Level::First].is_empty());
+        assert_eq!(map[Level::Second], ["second", "2nd"]);
+        assert!(map[Level::Third].is_empty());
+    }
+
+    #[test]
+    fn iterate_in_variant_order() {
+        let mut map = EnumMap::new();
+        map.insert(Level::Third, 3);
+        map.insert(Level::First, 1);
+        map.insert(Level::Second, 2);
+
+        let order: Vec<_> = Level::iter().collect();
+        assert_eq!(map.iter().map(|(level, _)| level).collect::<Vec<_>>(), order);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(Level::First, 1), (Level::Second, 2), (Level::Third, 3)]);
+    }
+
+    #[test]
+    fn mutate_through_iter_mut() {
+        let mut map = EnumMap::new();
+        map.insert(Level::First, 1);
+        map.insert(Level::Third, 3);
+
+        for (level, value) in map.iter_mut() {
+            *value += level as i32 * 100;
+        }
+
+        assert_eq!(map.get(Level::First), Some(&1));
+        assert_eq!(map.get(Level::Second), None);
+        assert_eq!(map.get(Level::Third), Some(&203));
+    }
+
+    #[test]
+    fn round_trip_through_hashmaps() {
+        let map = EnumMap::from_hashmap_or_default(HashMap::new(), |level| level as i32);
+
+        let hash_map = map.to_hashmap();
+        assert!(crate::validate_hash_map(&hash_map));
+        assert_eq!(EnumMap::from(hash_map), map);
+
+        let std_hash_map = map.to_std_hashmap();
+        assert!(crate::validate_hash_map(&std_hash_map));
+        for level in Level::iter() {
+            assert_eq!(std_hash_map.get(&level), map.get(level));
+        }
+    }
+
+    #[test]
+    fn partial_map_to_hashmap() {
+        let mut map = EnumMap::new();
+        map.insert(Level::Second, "second");
+
+        let hash_map = map.to_hashmap();
+        assert!(!crate::validate_hash_map(&hash_map));
+        assert_eq!(hash_map.len(), 1);
+        assert_eq!(hash_map[&Level::Second], "second");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let map = EnumMap::from_hashmap_or_default(HashMap::new(), |level| level as u8);
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"First":0,"Second":1,"Third":2}"#);
+        assert_eq!(serde_json::from_str::<EnumMap<Level, u8>>(&json).unwrap(), map);
+        assert_eq!(
+            serde_json::from_str::<EnumMap<Level, u8>>(r#"{"Third":2,"First":0,"Second":1}"#).unwrap(),
+            map
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_incomplete_or_unknown() {
+        let missing = serde_json::from_str::<EnumMap<Level, u8>>(r#"{"First":0,"Third":2}"#).unwrap_err();
+        assert!(missing.to_string().contains("missing variant `Second`"));
+
+        let unknown = serde_json::from_str::<EnumMap<Level, u8>>(r#"{"First":0,"Fourth":3}"#).unwrap_err();
+        assert!(unknown.to_string().contains("unknown variant `Fourth`"));
+
+        let duplicate = serde_json::from_str::<EnumMap<Level, u8>>(r#"{"First":0,"First":1}"#).unwrap_err();
+        assert!(duplicate.to_string().contains("duplicate variant `First`"));
+    }
+
+    #[test]
+    fn enum_index_matches_variant_order() {
+        for (position, level) in Level::iter().enumerate() {
+            assert_eq!(level.enum_index(), Some(position));
+        }
+    }
+
+    #[test]
+    fn from_hashmap_drops_disabled_keys() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert(Toggle::On, 1);
+        hash_map.insert(Toggle::Off, 2);
+        hash_map.insert(Toggle::Auto, 3);
+
+        let map = EnumMap::from(hash_map);
+        assert_eq!(map.get(Toggle::Off), None);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(Toggle::On, 1), (Toggle::Auto, 3)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "key is not yielded by `IntoEnumIterator::iter`")]
+    fn insert_disabled_key() {
+        EnumMap::new().insert(Toggle::Off, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "`EnumIndex` disagrees with `IntoEnumIterator::iter` order")]
+    fn explicit_discriminants_are_rejected() {
+        #[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+        enum Priority {
+            Low = 10,
+            High = 20,
+        }
+
+        crate::impl_enum_index!(Priority);
+
+        EnumMap::<Priority, ()>::new();
+    }
+
+    #[test]
+    #[should_panic(expected = "key is not yielded by `IntoEnumIterator::iter`")]
+    fn index_disabled_key() {
+        let map = EnumIndexed::from_fn(|toggle: Toggle| toggle as u8);
+        assert_eq!(map[Toggle::Auto], 2);
+
+        let _ = map[Toggle::Off];
+    }
+}
@@ -0,0 +1,995 @@
+use std::{
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
+    fmt::{Debug, Write},
+    hash::{BuildHasher, Hash},
+};
+
+use bevy_utils::hashbrown::{hash_map::Entry as BevyEntry, HashMap as BevyHashMap};
+
+/// Extension methods for both [`std::collections::HashMap`] and Bevy's [`HashMap`](bevy_utils::HashMap).
+pub trait HashMapExt<K, V> {
+    /// Returns the value of the key, inserting [`V::default`](Default::default) first if it is absent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy_hectic_utils::hashmap::HashMapExt;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut inventory = HashMap::<&str, Vec<&str>>::new();
+    /// inventory.get_or_insert_default("bag").push("apple");
+    /// inventory.get_or_insert_default("bag").push("key");
+    ///
+    /// assert_eq!(inventory["bag"], ["apple", "key"]);
+    /// ```
+    fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default;
+
+    /// Inserts the value if the key is absent, otherwise leaves the map unchanged and returns the value back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy_hectic_utils::hashmap::HashMapExt;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut ports = HashMap::new();
+    /// assert_eq!(ports.insert_unique("http", 80), Ok(()));
+    /// assert_eq!(ports.insert_unique("http", 8080), Err(8080));
+    /// assert_eq!(ports["http"], 80);
+    /// ```
+    fn insert_unique(&mut self, key: K, value: V) -> Result<(), V>;
+
+    /// Removes every listed key, returning how many of them were present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy_hectic_utils::{hashmap, hashmap::HashMapExt};
+    ///
+    /// let mut features = hashmap!{ "shadows" => true, "bloom" => false, "fog" => false };
+    ///
+    /// assert_eq!(features.remove_all(["bloom", "fog", "motion blur"]), 2);
+    /// assert_eq!(features, hashmap!{ "shadows" => true });
+    /// ```
+    fn remove_all(&mut self, keys: impl IntoIterator<Item = K>) -> usize;
+
+    /// Converts the map into an array holding the value of the `i`-th variant of `K` at index `i`.
+    ///
+    /// Succeeds only if the map has exactly `N` entries keyed by the first `N` variants yielded by
+    /// [`IntoEnumIterator::iter`](strum::IntoEnumIterator::iter), otherwise returns the map back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy_hectic_utils::{hashmap, hashmap::HashMapExt};
+    /// use strum::EnumIter;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+    /// enum Layer {
+    ///     Background,
+    ///     Foreground,
+    /// }
+    ///
+    /// let depths = hashmap!{ Layer::Foreground => 1.0, Layer::Background => 0.0 };
+    /// let depths: [f32; 2] = depths.try_into_array().unwrap();
+    ///
+    /// assert_eq!(depths[Layer::Foreground as usize], 1.0);
+    /// ```
+    #[cfg(feature = "strum")]
+    fn try_into_array<const N: usize>(self) -> Result<[V; N], Self>
+    where
+        Self: Sized,
+        K: strum::IntoEnumIterator;
+}
+
+/// Implements [`HashMapExt`] for a hash map type, given its [`Entry`] type, so std and Bevy maps share one body
+macro_rules! impl_hash_map_ext {
+    ($map: ident, $entry: ident) => {
+        impl<K, V, S> HashMapExt<K, V> for $map<K, V, S>
+        where
+            K: Eq + Hash,
+            S: BuildHasher,
+        {
+            fn get_or_insert_default(&mut self, key: K) -> &mut V
+            where
+                V: Default,
+            {
+                self.entry(key).or_default()
+            }
+
+            fn insert_unique(&mut self, key: K, value: V) -> Result<(), V> {
+                match self.entry(key) {
+                    $entry::Occupied(_) => Err(value),
+                    $entry::Vacant(entry) => {
+                        entry.insert(value);
+                        Ok(())
+                    }
+                }
+            }
+
+            fn remove_all(&mut self, keys: impl IntoIterator<Item = K>) -> usize {
+                keys.into_iter().filter(|key| self.remove(key).is_some()).count()
+            }
+
+            #[cfg(feature = "strum")]
+            fn try_into_array<const N: usize>(mut self) -> Result<[V; N], Self>
+            where
+                K: strum::IntoEnumIterator,
+            {
+                if self.len() != N || K::iter().take(N).filter(|key| self.contains_key(key)).count() != N {
+                    return Err(self);
+                }
+
+                let mut variants = K::iter();
+                Ok(std::array::from_fn(|_| {
+                    let key = variants.next().expect("checked that there are `N` variants");
+                    self.remove(&key).expect("checked that every variant is present")
+                }))
+            }
+        }
+    };
+}
+
+impl_hash_map_ext!(HashMap, Entry);
+impl_hash_map_ext!(BevyHashMap, BevyEntry);
+
+/// Bevy's [`HashMap`](bevy_utils::HashMap) hashing with a seeded [`RandomState`](bevy_utils::RandomState).
+pub type SeededHashMap<K, V> = bevy_utils::hashbrown::HashMap<K, V, bevy_utils::RandomState>;
+
+/// Creates an empty map whose hasher only depends on `seed`.
+///
+/// Maps built with the same seed and the same inserts iterate in the same order on every run, so tests
+/// can snapshot iteration order. See [`seeded_hashmap!`](crate::seeded_hashmap!) for the macro form.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap::seeded_hashmap;
+///
+/// let mut map = seeded_hashmap(42);
+/// map.insert("apple", 1);
+///
+/// assert_eq!(map["apple"], 1);
+/// ```
+pub fn seeded_hashmap<K, V>(seed: u64) -> SeededHashMap<K, V> {
+    let state = bevy_utils::RandomState::with_seeds(seed, seed.rotate_left(16), seed.rotate_left(32), seed.rotate_left(48));
+    SeededHashMap::with_hasher(state)
+}
+
+/// Builds the reverse lookup of a map, mapping each value to its key.
+///
+/// If several keys share a value, the values collapse: only one of those keys is kept, and which one
+/// depends on the map's iteration order. Use [`try_invert_hashmap`] to detect that instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::invert_hashmap};
+///
+/// let ids = hashmap!{ "alice" => 1, "bob" => 2 };
+/// let names = invert_hashmap(&ids);
+///
+/// assert_eq!(names[&2], "bob");
+/// ```
+pub fn invert_hashmap<K, V>(map: &HashMap<K, V>) -> HashMap<V, K>
+where
+    K: Clone,
+    V: Eq + Hash + Clone,
+{
+    map.iter().map(|(key, value)| (value.clone(), key.clone())).collect()
+}
+
+/// Builds the reverse lookup of a map, failing if it would lose data.
+///
+/// Returns `Err` with the first value found to be shared by several keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::try_invert_hashmap};
+///
+/// let ids = hashmap!{ "alice" => 1, "bob" => 1 };
+///
+/// assert_eq!(try_invert_hashmap(&ids), Err(1));
+/// ```
+pub fn try_invert_hashmap<K, V>(map: &HashMap<K, V>) -> Result<HashMap<V, K>, V>
+where
+    K: Clone,
+    V: Eq + Hash + Clone,
+{
+    let mut inverted = HashMap::with_capacity(map.len());
+    for (key, value) in map {
+        match inverted.entry(value.clone()) {
+            Entry::Occupied(entry) => return Err(entry.key().clone()),
+            Entry::Vacant(entry) => {
+                entry.insert(key.clone());
+            }
+        }
+    }
+    Ok(inverted)
+}
+
+/// Merges `overlay` into `base`, calling `resolve(base_value, overlay_value)` for keys present in both.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::merge_hashmaps};
+///
+/// let base = hashmap!{ "gold" => 10, "wood" => 5 };
+/// let loot = hashmap!{ "gold" => 3, "gems" => 1 };
+///
+/// let total = merge_hashmaps(base, loot, |base, loot| base + loot);
+/// assert_eq!(total, hashmap!{ "gold" => 13, "wood" => 5, "gems" => 1 });
+/// ```
+pub fn merge_hashmaps<K, V>(mut base: HashMap<K, V>, overlay: HashMap<K, V>, resolve: impl Fn(V, V) -> V) -> HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    for (key, value) in overlay {
+        let value = match base.remove(&key) {
+            Some(base_value) => resolve(base_value, value),
+            None => value,
+        };
+        base.insert(key, value);
+    }
+    base
+}
+
+/// Merges `overlay` into `base`, preferring values of `overlay` for keys present in both.
+pub fn merge_hashmaps_overlay<K, V>(base: HashMap<K, V>, overlay: HashMap<K, V>) -> HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    merge_hashmaps(base, overlay, |_, overlay_value| overlay_value)
+}
+
+/// Merges two-level maps: outer keys present in both are merged with [`merge_hashmaps_overlay`], so values of
+/// `overlay` win on inner collisions.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::deep_merge};
+///
+/// let defaults = hashmap!{ "video" => hashmap!{ "vsync" => "on", "fps" => "60" } };
+/// let user = hashmap!{ "video" => hashmap!{ "fps" => "144" } };
+///
+/// let config = deep_merge(defaults, user);
+/// assert_eq!(config["video"], hashmap!{ "vsync" => "on", "fps" => "144" });
+/// ```
+pub fn deep_merge<K, K2, V>(base: HashMap<K, HashMap<K2, V>>, overlay: HashMap<K, HashMap<K2, V>>) -> HashMap<K, HashMap<K2, V>>
+where
+    K: Eq + Hash,
+    K2: Eq + Hash,
+{
+    merge_hashmaps(base, overlay, merge_hashmaps_overlay)
+}
+
+/// Splits a map into the entries matching `pred` and the rest, without cloning them.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::partition_hashmap};
+///
+/// let features = hashmap!{ "shadows" => true, "bloom" => false, "fog" => true };
+/// let (enabled, disabled) = partition_hashmap(features, |_, enabled| *enabled);
+///
+/// assert_eq!(enabled.len(), 2);
+/// assert!(disabled.contains_key("bloom"));
+/// ```
+pub fn partition_hashmap<K, V>(map: HashMap<K, V>, pred: impl Fn(&K, &V) -> bool) -> (HashMap<K, V>, HashMap<K, V>)
+where
+    K: Eq + Hash,
+{
+    map.into_iter().partition(|(key, value)| pred(key, value))
+}
+
+/// Counts the entries matching `pred`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::count_matching};
+///
+/// let health = hashmap!{ "knight" => 80, "archer" => 0, "mage" => 0 };
+///
+/// assert_eq!(count_matching(&health, |_, health| *health == 0), 2);
+/// ```
+pub fn count_matching<K, V>(map: &HashMap<K, V>, pred: impl Fn(&K, &V) -> bool) -> usize {
+    map.iter().filter(|(key, value)| pred(key, value)).count()
+}
+
+/// Transforms every value of a map with `f`, keeping the keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::map_values};
+///
+/// let volumes = hashmap!{ "music" => 50, "effects" => 100 };
+/// let volumes = map_values(volumes, |percent| percent as f32 / 100.0);
+///
+/// assert_eq!(volumes["music"], 0.5);
+/// ```
+pub fn map_values<K, V, W>(map: HashMap<K, V>, f: impl Fn(V) -> W) -> HashMap<K, W>
+where
+    K: Eq + Hash,
+{
+    map.into_iter().map(|(key, value)| (key, f(value))).collect()
+}
+
+/// Transforms every key of a map with `f`, keeping the values.
+///
+/// Keys which `f` maps to the same key collapse into one entry holding one of their values, unspecified which.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::map_keys};
+///
+/// let scores = hashmap!{ "alice" => 3, "bob" => 5 };
+/// let scores = map_keys(scores, str::to_uppercase);
+///
+/// assert_eq!(scores["BOB"], 5);
+/// ```
+pub fn map_keys<K, L, V>(map: HashMap<K, V>, f: impl Fn(K) -> L) -> HashMap<L, V>
+where
+    L: Eq + Hash,
+{
+    map.into_iter().map(|(key, value)| (f(key), value)).collect()
+}
+
+/// Groups the items by the key computed by `key_fn`, keeping the input order within each group.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap::group_by;
+///
+/// let words = group_by(["apple", "bean", "avocado"], |word| word.chars().next());
+///
+/// assert_eq!(words[&Some('a')], ["apple", "avocado"]);
+/// assert_eq!(words[&Some('b')], ["bean"]);
+/// ```
+pub fn group_by<I, K, V>(iter: I, key_fn: impl Fn(&V) -> K) -> HashMap<K, Vec<V>>
+where
+    I: IntoIterator<Item = V>,
+    K: Eq + Hash,
+{
+    let mut groups = HashMap::<K, Vec<V>>::new();
+    for value in iter {
+        groups.entry(key_fn(&value)).or_default().push(value);
+    }
+    groups
+}
+
+/// Expands every group of a map into `(key, value)` pairs, undoing [`group_by`].
+///
+/// The groups come in the unspecified iteration order of the map, but each group keeps the order of its
+/// values.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap::{flatten_hashmap, group_by};
+///
+/// let words = group_by(["apple", "bean", "avocado"], |word| word.len() > 4);
+/// let mut pairs = flatten_hashmap(words);
+/// pairs.sort();
+///
+/// assert_eq!(pairs, [(false, "bean"), (true, "apple"), (true, "avocado")]);
+/// ```
+pub fn flatten_hashmap<K, V>(map: HashMap<K, Vec<V>>) -> Vec<(K, V)>
+where
+    K: Clone,
+{
+    map.into_iter()
+        .flat_map(|(key, values)| values.into_iter().map(move |value| (key.clone(), value)))
+        .collect()
+}
+
+/// Counts the occurrences of each item.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap::counter;
+///
+/// let letters = counter("hello".chars());
+///
+/// assert_eq!(letters[&'l'], 2);
+/// assert_eq!(letters[&'o'], 1);
+/// ```
+pub fn counter<I, T>(iter: I) -> HashMap<T, usize>
+where
+    I: IntoIterator<Item = T>,
+    T: Eq + Hash,
+{
+    let mut counts = HashMap::new();
+    for item in iter {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Same as [`counter`], but counts into Bevy's [`HashMap`](bevy_utils::HashMap).
+pub fn ns_counter<I, T>(iter: I) -> bevy_utils::HashMap<T, usize>
+where
+    I: IntoIterator<Item = T>,
+    T: Eq + Hash,
+{
+    let mut counts = bevy_utils::HashMap::new();
+    for item in iter {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Function form of [`hashmap!`](crate::hashmap!), building a map from an iterator of pairs.
+///
+/// Unlike the macro, it can be passed around and used in generic code.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap::hashmap_from_pairs;
+///
+/// let lengths = hashmap_from_pairs(["one", "three"].map(|word| (word, word.len())));
+///
+/// assert_eq!(lengths[&"three"], 5);
+/// ```
+pub fn hashmap_from_pairs<I, K, V>(pairs: I) -> HashMap<K, V>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Eq + Hash,
+{
+    pairs.into_iter().collect()
+}
+
+/// Same as [`hashmap_from_pairs`], but builds Bevy's [`HashMap`](bevy_utils::HashMap), see
+/// [`ns_hashmap!`](crate::ns_hashmap!).
+pub fn ns_hashmap_from_pairs<I, K, V>(pairs: I) -> bevy_utils::HashMap<K, V>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Eq + Hash,
+{
+    pairs.into_iter().collect()
+}
+
+/// Clones the entries of a map into a vector sorted by key, for deterministic output.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::sorted_entries};
+///
+/// let scores = hashmap!{ "bob" => 3, "alice" => 5 };
+///
+/// assert_eq!(sorted_entries(&scores), [("alice", 5), ("bob", 3)]);
+/// ```
+pub fn sorted_entries<K, V>(map: &HashMap<K, V>) -> Vec<(K, V)>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    let mut entries: Vec<_> = map.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+    entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+    entries
+}
+
+/// Clones the keys of a map into a sorted vector.
+pub fn sorted_keys<K, V>(map: &HashMap<K, V>) -> Vec<K>
+where
+    K: Ord + Clone,
+{
+    let mut keys: Vec<_> = map.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+/// Clones the values of a map into a vector, in the order of their sorted keys.
+pub fn sorted_values_by_key<K, V>(map: &HashMap<K, V>) -> Vec<V>
+where
+    K: Ord,
+    V: Clone,
+{
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries.into_iter().map(|(_, value)| value.clone()).collect()
+}
+
+/// Formats the entries of a map as `{key => value, ...}`, sorted by the [`Debug`] representation of the keys
+/// so the output is deterministic.
+///
+/// Takes anything iterating over `(&K, &V)`, e.g. a reference to a [`HashMap`]. Used by
+/// [`debug_hashmap!`](crate::debug_hashmap!).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::format_entries};
+///
+/// let volumes = hashmap!{ "music" => 0.5, "effects" => 1.0 };
+///
+/// assert_eq!(format_entries(&volumes), r#"{"effects" => 1.0, "music" => 0.5}"#);
+/// ```
+pub fn format_entries<'a, K, V>(entries: impl IntoIterator<Item = (&'a K, &'a V)>) -> String
+where
+    K: Debug + 'a,
+    V: Debug + 'a,
+{
+    let mut entries: Vec<_> = entries
+        .into_iter()
+        .map(|(key, value)| (format!("{key:?}"), value))
+        .collect();
+    entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let entries: Vec<_> = entries
+        .into_iter()
+        .map(|(key, value)| format!("{key} => {value:?}"))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) like [`hashmap!`](crate::hashmap!), logging its entries at
+/// debug level.
+///
+/// Entries are formatted by [`format_entries`](crate::hashmap::format_entries), and only if debug records are
+/// enabled, so without an initialized logger (see [`enable_logging`](crate::log::enable_logging)) it costs
+/// nothing but the map.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::debug_hashmap;
+///
+/// let capitals = debug_hashmap!{ "France" => "Paris", "Spain" => "Madrid" };
+///
+/// assert_eq!(capitals["Spain"], "Madrid");
+/// ```
+///
+/// # Notes
+///
+/// - Keys and values must implement [`Debug`].
+///
+#[macro_export]
+macro_rules! debug_hashmap {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        let map = $crate::hashmap! { $( $key => $val ),* };
+        if $crate::__private::log::log_enabled!($crate::__private::log::Level::Debug) {
+            $crate::__private::log::debug!("{}", $crate::hashmap::format_entries(&map));
+        }
+        map
+    }};
+}
+
+/// Describes the entries that differ between two maps, one line per key sorted by key, `None` if the maps
+/// are equal.
+///
+/// Takes anything iterating over `(&K, &V)`, e.g. a reference to a [`HashMap`], Bevy's
+/// [`HashMap`](bevy_utils::HashMap) or a [`BTreeMap`]. Used by [`assert_maps_eq!`](crate::assert_maps_eq!).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{hashmap, hashmap::describe_map_differences};
+///
+/// let left = hashmap!{ "a" => 1, "b" => 2 };
+/// let right = hashmap!{ "a" => 1, "b" => 3, "c" => 4 };
+///
+/// assert_eq!(
+///     describe_map_differences(&left, &right).unwrap(),
+///     "  \"b\": 2 != 3\n  \"c\": only in right, 4\n",
+/// );
+/// assert_eq!(describe_map_differences(&left, &left), None);
+/// ```
+pub fn describe_map_differences<'a, K, V>(
+    left: impl IntoIterator<Item = (&'a K, &'a V)>,
+    right: impl IntoIterator<Item = (&'a K, &'a V)>,
+) -> Option<String>
+where
+    K: Ord + Debug + 'a,
+    V: PartialEq + Debug + 'a,
+{
+    let left: BTreeMap<_, _> = left.into_iter().collect();
+    let right: BTreeMap<_, _> = right.into_iter().collect();
+    let keys: BTreeSet<_> = left.keys().chain(right.keys()).collect();
+
+    let mut differences = String::new();
+    for key in keys {
+        let _ = match (left.get(key), right.get(key)) {
+            (Some(left), Some(right)) if left != right => writeln!(differences, "  {key:?}: {left:?} != {right:?}"),
+            (Some(left), None) => writeln!(differences, "  {key:?}: only in left, {left:?}"),
+            (None, Some(right)) => writeln!(differences, "  {key:?}: only in right, {right:?}"),
+            _ => Ok(()),
+        };
+    }
+    (!differences.is_empty()).then_some(differences)
+}
+
+/// Asserts that two maps are equal, listing only the differing entries on failure.
+///
+/// Keys must implement [`Ord`] and [`Debug`], values [`PartialEq`] and [`Debug`], see
+/// [`describe_map_differences`](crate::hashmap::describe_map_differences).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{assert_maps_eq, hashmap, ns_hashmap};
+///
+/// assert_maps_eq!(hashmap!{ "a" => 1, "b" => 2 }, ns_hashmap!{ "b" => 2, "a" => 1 });
+/// ```
+///
+/// # Panics
+///
+/// Panics if the maps differ, with one line per differing key sorted by key.
+///
+#[macro_export]
+macro_rules! assert_maps_eq {
+    ($left: expr, $right: expr $(,)?) => {
+        if let ::core::option::Option::Some(differences) = $crate::hashmap::describe_map_differences(&$left, &$right) {
+            panic!("maps are not equal:\n{}", differences);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashmap;
+
+    #[test]
+    fn invert_injective() {
+        let map = hashmap! { "a" => 1, "b" => 2 };
+        let expected = hashmap! { 1 => "a", 2 => "b" };
+
+        assert_eq!(invert_hashmap(&map), expected);
+        assert_eq!(try_invert_hashmap(&map), Ok(expected));
+    }
+
+    #[test]
+    fn invert_non_injective() {
+        let map = hashmap! { "a" => 1, "b" => 1, "c" => 2 };
+
+        let inverted = invert_hashmap(&map);
+        assert_eq!(inverted.len(), 2);
+        assert!(inverted[&1] == "a" || inverted[&1] == "b");
+        assert_eq!(try_invert_hashmap(&map), Err(1));
+    }
+
+    #[test]
+    fn merge_disjoint_keys() {
+        let merged = merge_hashmaps(hashmap! { "a" => 1 }, hashmap! { "b" => 2 }, |_, _| unreachable!());
+
+        assert_eq!(merged, hashmap! { "a" => 1, "b" => 2 });
+    }
+
+    #[test]
+    fn merge_overlapping_keys() {
+        let base = hashmap! { "a" => 1, "b" => 2 };
+        let overlay = hashmap! { "b" => 3, "c" => 4 };
+
+        assert_eq!(
+            merge_hashmaps(base.clone(), overlay.clone(), |base, overlay| base + overlay),
+            hashmap! { "a" => 1, "b" => 5, "c" => 4 },
+        );
+        assert_eq!(merge_hashmaps_overlay(base, overlay), hashmap! { "a" => 1, "b" => 3, "c" => 4 });
+    }
+
+    #[test]
+    fn merge_empty() {
+        let empty = HashMap::<&str, i32>::new();
+
+        assert_eq!(merge_hashmaps_overlay(empty.clone(), empty.clone()), empty);
+        assert_eq!(merge_hashmaps_overlay(hashmap! { "a" => 1 }, empty.clone()), hashmap! { "a" => 1 });
+        assert_eq!(merge_hashmaps_overlay(empty, hashmap! { "a" => 1 }), hashmap! { "a" => 1 });
+    }
+
+    #[test]
+    fn group_by_parity() {
+        let groups = group_by(1..=6, |number| number % 2 == 0);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&false], [1, 3, 5]);
+        assert_eq!(groups[&true], [2, 4, 6]);
+    }
+
+    #[test]
+    fn group_by_keeps_order() {
+        let groups = group_by([3, 1, 4, 1, 5, 9, 2, 6], |number| *number > 3);
+
+        assert_eq!(groups[&false], [3, 1, 1, 2]);
+        assert_eq!(groups[&true], [4, 5, 9, 6]);
+        assert!(group_by(Vec::<i32>::new(), |number| *number).is_empty());
+    }
+
+    #[test]
+    fn counter_counts_chars() {
+        let counts = counter("mississippi".chars());
+
+        assert_eq!(counts, hashmap! { 'm' => 1, 'i' => 4, 's' => 4, 'p' => 2 });
+        assert_eq!(ns_counter("mississippi".chars()).into_iter().collect::<HashMap<_, _>>(), counts);
+    }
+
+    #[test]
+    fn counter_empty() {
+        assert!(counter(Vec::<char>::new()).is_empty());
+        assert!(ns_counter(Vec::<char>::new()).is_empty());
+    }
+
+    #[test]
+    fn from_pairs_vec() {
+        let pairs = vec![("a", 1), ("b", 2)];
+
+        assert_eq!(hashmap_from_pairs(pairs.clone()), hashmap! { "a" => 1, "b" => 2 });
+        assert_eq!(ns_hashmap_from_pairs(pairs), crate::ns_hashmap! { "a" => 1, "b" => 2 });
+    }
+
+    #[test]
+    fn from_pairs_mapped_iterator() {
+        let squares = hashmap_from_pairs((1..=3).map(|number| (number, number * number)));
+
+        assert_eq!(squares, hashmap! { 1 => 1, 2 => 4, 3 => 9 });
+        assert!(hashmap_from_pairs(Vec::<(i32, i32)>::new()).is_empty());
+    }
+
+    #[test]
+    fn get_or_insert_default_inserts_new() {
+        let mut map = HashMap::<&str, i32>::new();
+        *map.get_or_insert_default("a") += 2;
+
+        assert_eq!(map, hashmap! { "a" => 2 });
+
+        let mut map = bevy_utils::HashMap::<&str, i32>::new();
+        *map.get_or_insert_default("a") += 2;
+
+        assert_eq!(map, crate::ns_hashmap! { "a" => 2 });
+    }
+
+    #[test]
+    fn get_or_insert_default_returns_existing() {
+        let mut map = hashmap! { "a" => 1 };
+        *map.get_or_insert_default("a") += 2;
+
+        assert_eq!(map, hashmap! { "a" => 3 });
+
+        let mut map = crate::ns_hashmap! { "a" => 1 };
+        *map.get_or_insert_default("a") += 2;
+
+        assert_eq!(map, crate::ns_hashmap! { "a" => 3 });
+    }
+
+    #[test]
+    fn seeded_hashmap_iterates_identically() {
+        let build = |seed| {
+            let mut map = seeded_hashmap(seed);
+            for number in 0..64 {
+                map.insert(number, number * 2);
+            }
+            map.into_iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(build(7), build(7));
+        assert_eq!(
+            crate::seeded_hashmap!(7; "a" => 1, "b" => 2, "c" => 3).into_iter().collect::<Vec<_>>(),
+            crate::seeded_hashmap!(7; "a" => 1, "b" => 2, "c" => 3).into_iter().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn partition_by_threshold() {
+        let map = hashmap! { "a" => 1, "b" => 5, "c" => 10, "d" => 3 };
+        let len = map.len();
+
+        let (high, low) = partition_hashmap(map, |_, value| *value >= 5);
+        assert_eq!(high, hashmap! { "b" => 5, "c" => 10 });
+        assert_eq!(low, hashmap! { "a" => 1, "d" => 3 });
+        assert_eq!(high.len() + low.len(), len);
+    }
+
+    #[test]
+    fn partition_by_key() {
+        let (vowels, consonants) = partition_hashmap(hashmap! { 'a' => 1, 'b' => 2 }, |key, _| "aeiou".contains(*key));
+
+        assert_eq!(vowels, hashmap! { 'a' => 1 });
+        assert_eq!(consonants, hashmap! { 'b' => 2 });
+    }
+
+    #[test]
+    fn insert_unique_new_key() {
+        let mut map = hashmap! { "a" => 1 };
+        assert_eq!(map.insert_unique("b", 2), Ok(()));
+        assert_eq!(map, hashmap! { "a" => 1, "b" => 2 });
+
+        let mut map = crate::ns_hashmap! { "a" => 1 };
+        assert_eq!(map.insert_unique("b", 2), Ok(()));
+        assert_eq!(map, crate::ns_hashmap! { "a" => 1, "b" => 2 });
+    }
+
+    #[test]
+    fn insert_unique_collision() {
+        let mut map = hashmap! { "a" => 1 };
+        assert_eq!(map.insert_unique("a", 2), Err(2));
+        assert_eq!(map, hashmap! { "a" => 1 });
+
+        let mut map = crate::ns_hashmap! { "a" => 1 };
+        assert_eq!(map.insert_unique("a", 2), Err(2));
+        assert_eq!(map, crate::ns_hashmap! { "a" => 1 });
+    }
+
+    #[test]
+    fn deep_merge_disjoint_outer_keys() {
+        let merged = deep_merge(hashmap! { "a" => hashmap! { 1 => 'x' } }, hashmap! { "b" => hashmap! { 1 => 'y' } });
+
+        assert_eq!(merged, hashmap! { "a" => hashmap! { 1 => 'x' }, "b" => hashmap! { 1 => 'y' } });
+    }
+
+    #[test]
+    fn deep_merge_disjoint_inner_keys() {
+        let merged = deep_merge(hashmap! { "a" => hashmap! { 1 => 'x' } }, hashmap! { "a" => hashmap! { 2 => 'y' } });
+
+        assert_eq!(merged, hashmap! { "a" => hashmap! { 1 => 'x', 2 => 'y' } });
+    }
+
+    #[test]
+    fn deep_merge_overlapping_keys() {
+        let base = hashmap! { "a" => hashmap! { 1 => 'x', 2 => 'x' }, "b" => hashmap! { 1 => 'x' } };
+        let overlay = hashmap! { "a" => hashmap! { 1 => 'y', 2 => 'y' }, "b" => hashmap! { 1 => 'y' } };
+
+        assert_eq!(deep_merge(base, overlay.clone()), overlay);
+    }
+
+    #[test]
+    fn assert_maps_eq_equal_maps() {
+        crate::assert_maps_eq!(hashmap! { "a" => 1, "b" => 2 }, hashmap! { "b" => 2, "a" => 1 });
+    }
+
+    #[test]
+    fn assert_maps_eq_names_differing_key() {
+        let panic = std::panic::catch_unwind(|| {
+            crate::assert_maps_eq!(hashmap! { "a" => 1, "b" => 2, "c" => 3 }, hashmap! { "a" => 1, "b" => 5, "c" => 3 });
+        })
+        .unwrap_err();
+
+        assert_eq!(panic.downcast_ref::<String>().unwrap(), "maps are not equal:\n  \"b\": 2 != 5\n");
+    }
+
+    #[test]
+    fn describe_map_differences_sorted_by_key() {
+        let left = hashmap! { 3 => 'c', 1 => 'a', 2 => 'b' };
+        let right = hashmap! { 4 => 'd', 2 => 'x', 1 => 'a' };
+
+        assert_eq!(
+            describe_map_differences(&left, &right).unwrap(),
+            "  2: 'b' != 'x'\n  3: only in left, 'c'\n  4: only in right, 'd'\n",
+        );
+    }
+
+    #[test]
+    fn sorted_helpers() {
+        let map = hashmap! { 3 => 'c', 1 => 'a', 4 => 'd', 2 => 'b' };
+
+        assert_eq!(sorted_entries(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+        assert_eq!(sorted_keys(&map), [1, 2, 3, 4]);
+        assert_eq!(sorted_values_by_key(&map), ['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn sorted_helpers_empty() {
+        let map = HashMap::<i32, char>::new();
+
+        assert!(sorted_entries(&map).is_empty());
+        assert!(sorted_keys(&map).is_empty());
+        assert!(sorted_values_by_key(&map).is_empty());
+    }
+
+    #[test]
+    fn format_entries_sorted_by_key() {
+        assert_eq!(format_entries(&hashmap! { 'b' => 2, 'c' => 3, 'a' => 1 }), "{'a' => 1, 'b' => 2, 'c' => 3}");
+        assert_eq!(format_entries(&HashMap::<i32, i32>::new()), "{}");
+    }
+
+    #[cfg(feature = "strum")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumIter)]
+    enum Slot {
+        First,
+        Second,
+        Third,
+    }
+
+    #[cfg(feature = "strum")]
+    #[test]
+    fn try_into_array_complete() {
+        let map = hashmap! { Slot::Third => 'c', Slot::First => 'a', Slot::Second => 'b' };
+        assert_eq!(map.try_into_array(), Ok(['a', 'b', 'c']));
+
+        let mut map = bevy_utils::HashMap::default();
+        map.insert(Slot::First, 1);
+        assert_eq!(map.try_into_array(), Ok([1]));
+    }
+
+    #[cfg(feature = "strum")]
+    #[test]
+    fn try_into_array_mismatched_length() {
+        let map = hashmap! { Slot::First => 'a', Slot::Second => 'b' };
+
+        assert_eq!(map.clone().try_into_array::<3>(), Err(map.clone()));
+        assert_eq!(map.clone().try_into_array::<1>(), Err(map.clone()));
+        assert_eq!(hashmap! { Slot::First => 'a', Slot::Third => 'c' }.try_into_array::<2>().map_err(|map| map.len()), Err(2));
+        assert_eq!(map.clone().try_into_array::<4>(), Err(map));
+    }
+
+    #[test]
+    fn map_values_keeps_keys() {
+        let map = hashmap! { "a" => 1, "b" => 2 };
+
+        assert_eq!(map_values(map, |value| value.to_string()), hashmap! { "a" => "1".to_string(), "b" => "2".to_string() });
+    }
+
+    #[test]
+    fn map_keys_remaps() {
+        let map = hashmap! { 1 => 'a', 2 => 'b' };
+        assert_eq!(map_keys(map, |key| key * 10), hashmap! { 10 => 'a', 20 => 'b' });
+
+        let collapsed = map_keys(hashmap! { 1 => 'a', 3 => 'b', 4 => 'c' }, |key| key % 2);
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[&0], 'c');
+        assert!(['a', 'b'].contains(&collapsed[&1]));
+    }
+
+    #[test]
+    fn remove_all_counts_present_keys() {
+        let mut map = hashmap! { "a" => 1, "b" => 2, "c" => 3 };
+
+        assert_eq!(map.remove_all(["a", "x", "c", "a"]), 2);
+        assert_eq!(map, hashmap! { "b" => 2 });
+        assert_eq!(map.remove_all([]), 0);
+
+        let mut map = bevy_utils::HashMap::default();
+        map.insert(1, 'a');
+        map.insert(2, 'b');
+        assert_eq!(map.remove_all(0..10), 2);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn count_matching_threshold() {
+        let map = hashmap! { "a" => 1, "b" => 5, "c" => 10 };
+
+        assert_eq!(count_matching(&map, |_, value| *value > 3), 2);
+        assert_eq!(count_matching(&map, |key, value| *key == "a" && *value > 3), 0);
+    }
+
+    #[test]
+    fn count_matching_all_or_none() {
+        let map = hashmap! { "a" => 1, "b" => 2 };
+
+        assert_eq!(count_matching(&map, |_, _| true), map.len());
+        assert_eq!(count_matching(&map, |_, _| false), 0);
+        assert_eq!(count_matching(&HashMap::<i32, i32>::new(), |_, _| true), 0);
+    }
+
+    #[test]
+    fn flatten_keeps_group_order() {
+        let map = hashmap! { 'a' => vec![3, 1, 2], 'b' => vec![], 'c' => vec![5] };
+        let pairs = flatten_hashmap(map);
+
+        assert_eq!(pairs.len(), 4);
+        assert!(pairs.contains(&('c', 5)));
+        let a: Vec<_> = pairs.iter().filter(|(key, _)| *key == 'a').map(|(_, value)| *value).collect();
+        assert_eq!(a, [3, 1, 2]);
+    }
+
+    #[test]
+    fn flatten_undoes_group_by() {
+        let words = ["apple", "bean", "avocado", "banana", "cherry"];
+        let mut pairs = flatten_hashmap(group_by(words, |word| word.chars().next().unwrap()));
+        pairs.sort();
+
+        assert_eq!(pairs, [('a', "apple"), ('a', "avocado"), ('b', "banana"), ('b', "bean"), ('c', "cherry")]);
+    }
+}
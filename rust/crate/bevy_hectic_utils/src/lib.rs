@@ -1,8 +1,138 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 #[cfg(feature = "strum")]
-use bevy_utils::hashbrown::HashMap;
+use std::str::FromStr;
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, hash::{BuildHasher, Hash}};
+
+#[cfg(feature = "std")]
+use bevy_utils::hashbrown::{HashMap, HashSet};
 #[cfg(feature = "strum")]
 use strum::IntoEnumIterator;
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+#[cfg(feature = "std")]
+pub mod hashmap;
+#[cfg(feature = "std")]
+pub mod log;
+pub mod prelude;
+
+#[cfg(feature = "strum")]
+mod enum_map;
+#[cfg(test)]
+mod test_env;
+
+/// Dependencies used by the exported macros, so they resolve regardless of the caller's dependencies and imports
+#[doc(hidden)]
+pub mod __private {
+    pub use ::alloc::collections::BTreeMap;
+    #[cfg(feature = "std")]
+    pub use ::std::collections;
+    #[cfg(feature = "std")]
+    pub use bevy_utils;
+    #[cfg(feature = "no_std")]
+    pub use hashbrown;
+    #[cfg(feature = "indexmap")]
+    pub use indexmap;
+    pub use ::log;
+    #[cfg(feature = "phf")]
+    pub use phf;
+    #[cfg(feature = "strum")]
+    pub use strum;
+}
+
+#[cfg(feature = "std")]
+pub use bench::Times;
+#[cfg(feature = "strum")]
+pub use enum_map::{EnumIndex, EnumIndexed, EnumMap};
+
+/// Read-only view over the keys of a map, used to validate maps regardless of their concrete type.
+///
+/// Implemented for Bevy's [`HashMap`](bevy_utils::HashMap), [`std::collections::HashMap`] and
+/// [`BTreeMap`], as well as for Bevy's [`HashSet`](bevy_utils::HashSet) and [`std::collections::HashSet`],
+/// whose elements are their keys.
+#[cfg(feature = "std")]
+pub trait MapKeys {
+    /// The type of the keys in the map.
+    type Key;
+
+    /// Returns `true` if the map contains a value for the specified key.
+    fn contains_key(&self, key: &Self::Key) -> bool;
+
+    /// Returns the number of keys in the map.
+    fn key_count(&self) -> usize;
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, S: BuildHasher> MapKeys for HashMap<K, V, S> {
+    type Key = K;
+
+    fn contains_key(&self, key: &K) -> bool {
+        HashMap::contains_key(self, key)
+    }
+
+    fn key_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, S: BuildHasher> MapKeys for std::collections::HashMap<K, V, S> {
+    type Key = K;
+
+    fn contains_key(&self, key: &K) -> bool {
+        std::collections::HashMap::contains_key(self, key)
+    }
+
+    fn key_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, S: BuildHasher> MapKeys for HashSet<K, S> {
+    type Key = K;
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+
+    fn key_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, S: BuildHasher> MapKeys for std::collections::HashSet<K, S> {
+    type Key = K;
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+
+    fn key_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Ord, V> MapKeys for BTreeMap<K, V> {
+    type Key = K;
+
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+
+    fn key_count(&self) -> usize {
+        self.len()
+    }
+}
+
 /// Validates that a given hash map contains exactly one of each possible key as defined by the key type `K`.
 ///
 /// This function checks whether the provided `hash_map` contains exactly one of each possible key.
@@ -12,12 +142,10 @@ use strum::IntoEnumIterator;
 ///
 /// # Type Parameters
 ///
-/// * `K`: The type of the keys in the `HashMap`. It must satisfy the following conditions:
-///   * `Eq`: Allows comparing keys for equality.
-///   * `std::hash::Hash`: Necessary for the keys to be hashed, a requirement in a `HashMap`.
-///   * `Copy`: Indicates that the keys can be copied, which is used in iterating through keys.
-///   * `IntoEnumIterator`: Provides an iterator over all possible values of `K`.
-/// * `V`: The type of the values in the `HashMap`. There are no specific trait bounds for `V` in this function.
+/// * `M`: The type of the map, either Bevy's [`HashMap`](bevy_utils::HashMap) or
+///   [`std::collections::HashMap`] (anything implementing [`MapKeys`]).
+/// * `M::Key`: The type of the keys in the map. It must implement `IntoEnumIterator`, which provides an
+///   iterator over all possible values of the key.
 ///
 /// # Parameters
 ///
@@ -28,9 +156,18 @@ use strum::IntoEnumIterator;
 /// Returns `true` if the `hash_map` contains exactly one of each possible key (as defined by the key type `K`),
 /// and no more. Otherwise, it returns `false`.
 ///
+/// Possible keys are the ones yielded by [`IntoEnumIterator::iter`], so variants marked `#[strum(disabled)]`
+/// are not expected: a map without them is valid, and a map with them has extra keys, see
+/// [`HashMapDiff::extra`].
+///
 /// # Examples
 ///
 /// ```rust
+/// use bevy_hectic_utils::validate_hash_map;
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 /// enum LevelState {
 ///     Level1,
 ///     Level2,
@@ -45,277 +182,2100 @@ use strum::IntoEnumIterator;
 /// }
 /// 
 /// fn main() {
-///     let mut hash_map = HashMap::new();
-///     hash_map.insert(State::State1, load_level_1);
-///     hash_map.insert(State::State2, load_level_2);
+///     let mut hash_map: HashMap<LevelState, fn()> = HashMap::new();
+///     hash_map.insert(LevelState::Level1, load_level_1);
+///     hash_map.insert(LevelState::Level2, load_level_2);
 ///     
-///     use bevy_hectic_utils::hashmap::*;
-///     assert!(validate_hash_map(hash_map)); // Returns true
+///     assert!(validate_hash_map(&hash_map)); // Returns true
 /// 
 ///     // Code where you sure that the hash map contains exactly one of each possible key ...
 /// }
 /// ```
 #[cfg(feature = "strum")]
-pub fn validate_hash_map<K, V>(hash_map: &HashMap<K, V>) -> bool
+pub fn validate_hash_map<M>(hash_map: &M) -> bool
 where
-    K: Eq + std::hash::Hash + Copy + IntoEnumIterator,
-    K::Iterator: Iterator<Item = K>,
+    M: MapKeys,
+    M::Key: IntoEnumIterator,
 {
-    let all_keys = K::iter().collect::<Vec<_>>();
-    if hash_map.len() != all_keys.len() {
-        return false;
-    }
-
-    for key in all_keys {
-        if !hash_map.contains_key(&key) {
-            return false;
-        }
-    }
+    diff_hash_map(hash_map).is_complete()
+}
 
-    true
+/// Validates that a given [`BTreeMap`] contains exactly one of each possible key as defined by the key type `K`.
+///
+/// This is the [`BTreeMap`] flavour of [`validate_hash_map`], for tables that are kept ordered.
+/// An enum without variants only validates an empty map.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use bevy_hectic_utils::validate_btree_map;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+/// enum Stage {
+///     Load,
+///     Run,
+/// }
+///
+/// let mut map = BTreeMap::new();
+/// map.insert(Stage::Load, "load");
+/// assert!(!validate_btree_map(&map));
+///
+/// map.insert(Stage::Run, "run");
+/// assert!(validate_btree_map(&map));
+/// ```
+#[cfg(feature = "strum")]
+pub fn validate_btree_map<K, V>(map: &BTreeMap<K, V>) -> bool
+where
+    K: Ord + IntoEnumIterator,
+{
+    validate_hash_map(map)
 }
 
-/// Asserts that a given hash map contains exactly one of each possible key.
+/// Validates that a given hash set contains exactly every possible value of `K`.
 ///
-/// This macro is a convenience wrapper around the [`validate_hash_map`] function, intended to be used in 
-/// tests or other scenarios where you want to ensure that a `HashMap` is fully populated with no missing 
-/// or extra elements and panic otherwise. It's equivalent to `assert!(validate_hash_map(hash_map));`.
+/// Set-shaped counterpart of [`validate_hash_map`], e.g. for a set of enabled states that must list all of them.
 ///
-/// # Usage
+/// # Examples
 ///
 /// ```rust
-/// use bevy_hectic_utils::validate_hash_map;
-/// 
-/// validate_hash_map!(hash_map);
+/// use bevy_hectic_utils::validate_hash_set;
+/// use bevy_utils::HashSet;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Channel {
+///     Music,
+///     Effects,
+/// }
+///
+/// let mut unlocked = HashSet::new();
+/// unlocked.insert(Channel::Music);
+/// assert!(!validate_hash_set(&unlocked));
+///
+/// unlocked.insert(Channel::Effects);
+/// assert!(validate_hash_set(&unlocked));
 /// ```
+#[cfg(feature = "strum")]
+pub fn validate_hash_set<K, S>(set: &HashSet<K, S>) -> bool
+where
+    K: Eq + Hash + IntoEnumIterator,
+    S: BuildHasher,
+{
+    validate_hash_map(set)
+}
+
+/// Describes how a hash map differs from containing exactly one of each possible key of `K`.
 ///
-/// # Panics
+/// Returned by [`diff_hash_map`].
+#[cfg(feature = "strum")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashMapDiff<K> {
+    /// Keys yielded by [`IntoEnumIterator::iter`] that are absent from the map, in iteration order.
+    pub missing: Vec<K>,
+    /// Number of keys in the map that [`IntoEnumIterator::iter`] does not yield
+    /// (e.g. variants marked with `#[strum(disabled)]`).
+    pub extra: usize,
+}
+
+#[cfg(feature = "strum")]
+impl<K> HashMapDiff<K> {
+    /// Returns `true` if nothing is missing and nothing is extra.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.extra == 0
+    }
+}
+
+/// Computes which keys of `K` a given hash map lacks.
 ///
-/// Panics if the `hash_map` does not contain exactly one of each possible key
-/// or if the `hash_map` contains more than one of any key.
+/// This is the detailed counterpart of [`validate_hash_map`]: instead of a plain `bool` it returns a
+/// [`HashMapDiff`] listing every missing key, which makes debugging a misconfigured map much easier.
 ///
 /// # Examples
 ///
 /// ```rust
+/// use bevy_hectic_utils::diff_hash_map;
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 /// enum LevelState {
 ///     Level1,
 ///     Level2,
 /// }
-/// 
-/// fn load_level_1(/* ... */) {
-/// // ...
-/// }
-/// 
-/// fn load_level_2(/* ... */) {
-/// // ...
-/// }
-/// 
-/// fn main() {
-///     let mut hash_map = HashMap::new();
-///     hash_map.insert(State::State1, load_level_1);
-///     hash_map.insert(State::State2, load_level_2);
-///     
-///     use bevy_hectic_utils::hashmap::*;
-///     validate_hash_map!(hash_map); // Returns true
-/// 
-///     // Code where you sure that the hash map contains exactly one of each possible key ...
-/// }
+///
+/// let mut hash_map = HashMap::new();
+/// hash_map.insert(LevelState::Level1, "level_1.scn");
+///
+/// let diff = diff_hash_map(&hash_map);
+/// assert_eq!(diff.missing, vec![LevelState::Level2]);
 /// ```
-#[macro_export]
 #[cfg(feature = "strum")]
-macro_rules! validate_hash_map {
-    ($hash_map:expr) => {
-        assert!(validate_hash_map($hash_map));
-    };
+pub fn diff_hash_map<M>(hash_map: &M) -> HashMapDiff<M::Key>
+where
+    M: MapKeys,
+    M::Key: IntoEnumIterator,
+{
+    let mut expected = 0;
+    let missing = M::Key::iter()
+        .inspect(|_| expected += 1)
+        .filter(|key| !hash_map.contains_key(key))
+        .collect::<Vec<_>>();
+    let extra = hash_map.key_count() - (expected - missing.len());
+
+    HashMapDiff { missing, extra }
 }
 
-/// Creates a [`HashMap`](bevy_utils::HashMap) using Bevy's hash maps for increased speed with less security.
+/// Error returned by [`check_hash_map`].
+#[cfg(feature = "strum")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashMapValidationError<K> {
+    /// Keys yielded by [`IntoEnumIterator::iter`] that are absent from the map, in iteration order.
+    MissingKeys(Vec<K>),
+    /// Nothing is missing, but the map has keys that [`IntoEnumIterator::iter`] does not yield.
+    WrongLength {
+        /// Number of keys yielded by [`IntoEnumIterator::iter`].
+        expected: usize,
+        /// Number of keys in the map.
+        actual: usize,
+    },
+}
+
+#[cfg(feature = "strum")]
+impl<K: std::fmt::Debug> std::fmt::Display for HashMapValidationError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingKeys(keys) => write!(f, "hash map is missing keys: {keys:?}"),
+            Self::WrongLength { expected, actual } => {
+                write!(f, "hash map has {actual} keys, expected {expected}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "strum")]
+impl<K: std::fmt::Debug> std::error::Error for HashMapValidationError<K> {}
+
+/// Checks that a given hash map contains exactly one of each possible key as defined by the key type.
 ///
-/// This macro initializes a [`HashMap`](bevy_utils::HashMap) with the specified key-value pairs. It is called "no secure" (ns)
-/// because it prioritizes performance potentially at the cost of certain security measures found
-/// in other hash maps. It's a convenient way to quickly create a populated [`HashMap`](bevy_utils::HashMap).
+/// This is the `Result` flavour of [`validate_hash_map`], for use in `Result`-returning code such as config
+/// loaders.
 ///
 /// # Examples
 ///
 /// ```rust
-/// use bevy_hectic_utils::ns_hashmap;
+/// use bevy_hectic_utils::{check_hash_map, HashMapValidationError};
 /// use bevy_utils::HashMap;
-/// 
-/// let fruits = ns_hashmap!{
-///     "apple" => 1,
-///     "banana" => 2
-/// };
-/// 
-/// // `fruits` is now a HashMap containing {"apple": 1, "banana": 2}
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum LevelState {
+///     Level1,
+///     Level2,
+/// }
+///
+/// let mut hash_map = HashMap::new();
+/// hash_map.insert(LevelState::Level1, "level_1.scn");
+///
+/// assert_eq!(
+///     check_hash_map(&hash_map),
+///     Err(HashMapValidationError::MissingKeys(vec![LevelState::Level2])),
+/// );
 /// ```
+#[cfg(feature = "strum")]
+pub fn check_hash_map<M>(hash_map: &M) -> Result<(), HashMapValidationError<M::Key>>
+where
+    M: MapKeys,
+    M::Key: IntoEnumIterator + std::fmt::Debug,
+{
+    let diff = diff_hash_map(hash_map);
+    if !diff.missing.is_empty() {
+        return Err(HashMapValidationError::MissingKeys(diff.missing));
+    }
+    if diff.extra > 0 {
+        let actual = hash_map.key_count();
+        return Err(HashMapValidationError::WrongLength { expected: actual - diff.extra, actual });
+    }
+
+    Ok(())
+}
+
+/// Returns every key of `K` that does not appear in `keys`, in [`IntoEnumIterator::iter`] order.
 ///
-/// # Notes
+/// Unlike [`diff_hash_map`], it isn't tied to a map: `keys` can be anything iterable over keys or references
+/// to them, such as a `Vec` or [`HashMap::keys`](bevy_utils::HashMap::keys).
 ///
-/// - This macro is a simple and performant way to instantiate a hash map, but it should not be used
-///   in security-sensitive contexts due to its lack of secure hashing.
+/// # Examples
 ///
-#[macro_export]
-macro_rules! ns_hashmap {
-    ($( $key: expr => $val: expr ),*) => {{
-        let mut map = HashMap::new();
-        $(
-            map.insert($key, $val);
-        )*
-        map
-    }};
+/// ```rust
+/// use bevy_hectic_utils::missing_enum_variants;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+/// enum Direction {
+///     Up,
+///     Down,
+/// }
+///
+/// assert_eq!(missing_enum_variants::<Direction, _>([Direction::Up]), vec![Direction::Down]);
+/// ```
+#[cfg(feature = "strum")]
+pub fn missing_enum_variants<K, I>(keys: I) -> Vec<K>
+where
+    K: IntoEnumIterator + PartialEq,
+    I: IntoIterator,
+    I::Item: std::borrow::Borrow<K>,
+{
+    use std::borrow::Borrow;
+
+    let keys = keys.into_iter().collect::<Vec<_>>();
+    K::iter()
+        .filter(|variant| !keys.iter().any(|key| key.borrow() == variant))
+        .collect()
 }
 
-/// Creates a [`HashMap`](`std::collections::HashMap`) from a list of key-value pairs.
+/// Asserts that every variant of an enum appears in a collection of keys.
 ///
-/// This macro simplifies the creation of a [`HashMap`](`std::collections::HashMap`) by allowing inline definition of key-value pairs.
-/// It initializes a [`HashMap`](`std::collections::HashMap`) using Rust's standard [`std::collections::HashMap`] and inserts the specified
-/// pairs into the map. It's a convenient way to quickly create and populate a [`HashMap`](`std::collections::HashMap`).
+/// This extends [`validate_hash_map!`] to any collection: the first argument is anything iterable over keys
+/// or references to them, the second one is the enum type. See [`missing_enum_variants`].
+///
+/// # Panics
+///
+/// Panics, listing the missing variants, if any variant of the enum does not appear in the collection.
 ///
 /// # Examples
 ///
 /// ```rust
-/// use bevy_hectic_utils::hashmap;
+/// use bevy_hectic_utils::assert_enum_exhaustive;
 /// use bevy_utils::HashMap;
-/// 
-/// let capitals = hashmap!{
-///     "France" => "Paris",
-///     "Spain" => "Madrid"
-/// };
-/// 
-/// // `capitals` is now a HashMap containing {"France": "Paris", "Spain": "Madrid"}
-/// ```
+/// use strum::EnumIter;
 ///
-/// # Notes
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Direction {
+///     Up,
+///     Down,
+/// }
 ///
-/// - This macro creates a mutable `HashMap` and fills it with the provided key-value pairs.
-/// - It is a shorthand for manually creating a `HashMap` and inserting each key and value.
+/// assert_enum_exhaustive!(vec![Direction::Down, Direction::Up], Direction);
 ///
+/// let mut arrows = HashMap::new();
+/// arrows.insert(Direction::Up, '↑');
+/// arrows.insert(Direction::Down, '↓');
+/// assert_enum_exhaustive!(arrows.keys(), Direction);
+/// ```
 #[macro_export]
-macro_rules! hashmap {
-    ($( $key: expr => $val: expr ),*) => {{
-        let mut map = std::hashmap::HashMap::new();
-        $(
-            map.insert($key, $val);
-        )*
-        map
+#[cfg(feature = "strum")]
+macro_rules! assert_enum_exhaustive {
+    ($keys:expr, $enum:ty $(,)?) => {{
+        let missing = $crate::missing_enum_variants::<$enum, _>($keys);
+        assert!(
+            missing.is_empty(),
+            "collection is missing variants of {}: {:?}",
+            stringify!($enum),
+            missing,
+        );
     }};
 }
 
+/// Expands to the number of variants of an enum as a `const` expression.
+///
+/// The enum must derive strum's `EnumCount`, which makes the count usable for array sizing, e.g. for fixed
+/// arrays indexed like an [`EnumMap`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::enum_variant_count;
+/// use strum::EnumCount;
+///
+/// #[derive(EnumCount)]
+/// enum Direction {
+///     Up,
+///     Down,
+///     Left,
+///     Right,
+/// }
+///
+/// let speeds = [0.0; enum_variant_count!(Direction)];
+/// assert_eq!(speeds.len(), 4);
+/// ```
+#[macro_export]
+#[cfg(feature = "strum")]
+macro_rules! enum_variant_count {
+    ($enum:ty) => {
+        <$enum as $crate::__private::strum::EnumCount>::COUNT
+    };
+}
 
-#[cfg(test)]
-pub mod test {
-    use std::{time::{Duration, Instant}, ops::{Deref, DerefMut}};
-
-    use log::Level;
+/// Asserts at compile time that the listed variants are exactly the variants of an enum.
+///
+/// A `match` over the listed variants makes a missing variant a non-exhaustive pattern error, and the
+/// number of listed variants is compared to [`EnumCount::COUNT`](strum::EnumCount::COUNT) to reject
+/// duplicates. Put it next to a map built by hand, so adding a variant breaks the build until the map
+/// is updated.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::static_assert_enum_map_keys;
+/// use strum::EnumCount;
+///
+/// #[derive(EnumCount)]
+/// enum Weapon {
+///     Sword,
+///     Bow(u32),
+///     Staff { element: char },
+/// }
+///
+/// static_assert_enum_map_keys!(Weapon, [Sword, Bow, Staff]);
+/// ```
+///
+/// # Notes
+///
+/// - Variants are listed by name only, their fields don't matter.
+/// - `EnumCount` doesn't count `#[strum(disabled)]` variants while the `match` requires them, so enums
+///   with disabled variants can't be checked.
+///
+#[macro_export]
+#[cfg(feature = "strum")]
+macro_rules! static_assert_enum_map_keys {
+    ($enum:ty, [$( $variant:ident ),* $(,)?]) => {
+        const _: () = {
+            type Enum = $enum;
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-    pub struct Times(u64);
+            #[allow(dead_code, unreachable_patterns)]
+            fn exhaustive(value: Enum) {
+                match value {
+                    $( Enum::$variant { .. } => {} )*
+                }
+            }
 
-    impl Deref for Times {
-        type Target = u64;
+            assert!(
+                [$( stringify!($variant) ),*].len() == <Enum as $crate::__private::strum::EnumCount>::COUNT,
+                "listed variants don't match the variants of the enum",
+            );
+        };
+    };
+}
 
-        fn deref(&self) -> &Self::Target {
-            &self.0
-        }
-    }
-    
-    impl DerefMut for Times {
-        fn deref_mut(&mut self) -> &mut Self::Target {
-            &mut self.0
-        }
-    }
+/// Builds a hash map containing exactly one value for each possible key of `K`.
+///
+/// Rather than building a map by hand and checking it with [`validate_hash_map`] afterwards, this function
+/// constructs it correctly by definition: `f` is called once for every key yielded by
+/// [`IntoEnumIterator::iter`], and its result becomes the value of that key.
+/// The returned map always passes [`validate_hash_map`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{build_enum_map, validate_hash_map};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum LevelState {
+///     Level1,
+///     Level2,
+/// }
+///
+/// let scenes = build_enum_map(|state| match state {
+///     LevelState::Level1 => "level_1.scn",
+///     LevelState::Level2 => "level_2.scn",
+/// });
+///
+/// assert!(validate_hash_map(&scenes));
+/// ```
+#[cfg(feature = "strum")]
+pub fn build_enum_map<K, V>(mut f: impl FnMut(K) -> V) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone + IntoEnumIterator,
+{
+    K::iter().map(|key| (key.clone(), f(key))).collect()
+}
 
-    impl Into<u64> for Times {
-        fn into(self) -> u64 {
-            self.0
+/// Fallible version of [`build_enum_map`].
+///
+/// Calls `f` for every key yielded by [`IntoEnumIterator::iter`], in iteration order, and stops at the first
+/// `Err`, returning it unchanged. Otherwise the returned map contains exactly one of each possible key and
+/// always passes [`validate_hash_map`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::try_build_enum_map;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Setting {
+///     Width,
+///     Height,
+/// }
+///
+/// let parsed = try_build_enum_map(|setting| match setting {
+///     Setting::Width => "800".parse::<u32>(),
+///     Setting::Height => "600".parse::<u32>(),
+/// });
+///
+/// assert_eq!(parsed.unwrap()[&Setting::Height], 600);
+/// ```
+#[cfg(feature = "strum")]
+pub fn try_build_enum_map<K, V, E>(mut f: impl FnMut(K) -> Result<V, E>) -> Result<HashMap<K, V>, E>
+where
+    K: Eq + Hash + Clone + IntoEnumIterator,
+{
+    K::iter().map(|key| Ok((key.clone(), f(key)?))).collect()
+}
+
+/// Builds a hash map mapping each possible key of `K` to `V::default()`.
+///
+/// It's [`build_enum_map`]`(|_| V::default())`, a common starting point for a map to be mutated later.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::default_enum_map;
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Team {
+///     Red,
+///     Blue,
+/// }
+///
+/// let mut scores: HashMap<Team, u32> = default_enum_map();
+/// *scores.get_mut(&Team::Red).unwrap() += 1;
+/// ```
+#[cfg(feature = "strum")]
+pub fn default_enum_map<K, V>() -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone + IntoEnumIterator,
+    V: Default,
+{
+    build_enum_map(|_| V::default())
+}
+
+/// Collects the values of a map in [`IntoEnumIterator::iter`] order of their keys.
+///
+/// The counterpart of [`build_enum_map`]: returns the list of missing keys instead if the map lacks any
+/// variant. Keys not yielded by [`IntoEnumIterator::iter`] are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{build_enum_map, collect_enum_values};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Stage {
+///     Idle,
+///     Run,
+///     Jump,
+/// }
+///
+/// let frames = build_enum_map(|stage: Stage| stage as usize * 4);
+///
+/// assert_eq!(collect_enum_values(&frames), Ok(vec![0, 4, 8]));
+/// ```
+#[cfg(feature = "strum")]
+pub fn collect_enum_values<K, V>(map: &HashMap<K, V>) -> Result<Vec<V>, Vec<K>>
+where
+    K: Eq + Hash + IntoEnumIterator,
+    V: Clone,
+{
+    let mut values = Vec::with_capacity(map.len());
+    let mut missing = Vec::new();
+    for key in K::iter() {
+        match map.get(&key) {
+            Some(value) => values.push(value.clone()),
+            None => missing.push(key),
+        }
+    }
+    if missing.is_empty() {
+        Ok(values)
+    } else {
+        Err(missing)
+    }
+}
+
+/// What [`for_each_enum_value`] does with a variant missing from the map.
+#[cfg(feature = "strum")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnMissing {
+    /// Skip the variant.
+    Skip,
+    /// Don't visit anything and return the first missing variant.
+    Fail,
+}
+
+/// Calls `f` with every variant of `K` and its value in the map, in [`IntoEnumIterator::iter`] order.
+///
+/// Unlike iterating the map itself, the order is deterministic. Variants missing from the map are handled
+/// according to `on_missing`: with [`OnMissing::Fail`], the map is checked before any call, so `f` is
+/// either called for every variant or not at all. Keys not yielded by [`IntoEnumIterator::iter`] are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{for_each_enum_value, ns_hashmap, OnMissing};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Layer {
+///     Background,
+///     World,
+///     Ui,
+/// }
+///
+/// let textures = ns_hashmap!{ Layer::Ui => "ui.png", Layer::Background => "sky.png" };
+///
+/// let mut drawn = Vec::new();
+/// for_each_enum_value(&textures, OnMissing::Skip, |_, texture| drawn.push(*texture)).unwrap();
+/// assert_eq!(drawn, ["sky.png", "ui.png"]);
+///
+/// assert_eq!(for_each_enum_value(&textures, OnMissing::Fail, |_, _| ()), Err(Layer::World));
+/// ```
+#[cfg(feature = "strum")]
+pub fn for_each_enum_value<K, V>(map: &HashMap<K, V>, on_missing: OnMissing, mut f: impl FnMut(K, &V)) -> Result<(), K>
+where
+    K: Eq + Hash + IntoEnumIterator,
+{
+    if on_missing == OnMissing::Fail {
+        if let Some(missing) = K::iter().find(|key| !map.contains_key(key)) {
+            return Err(missing);
+        }
+    }
+    for key in K::iter() {
+        if let Some(value) = map.get(&key) {
+            f(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Removes every entry whose key doesn't parse to a variant of `K`.
+///
+/// Useful to drop stale or bogus keys from a map deserialized from untrusted data before converting
+/// its keys to the enum.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::retain_enum_keys;
+/// use bevy_utils::HashMap;
+/// use strum::EnumString;
+///
+/// #[derive(Debug, EnumString)]
+/// enum Action {
+///     Jump,
+///     Crouch,
+/// }
+///
+/// let mut bindings = HashMap::new();
+/// bindings.insert("Jump".to_string(), "Space");
+/// bindings.insert("Fly".to_string(), "F");
+///
+/// retain_enum_keys::<Action, _>(&mut bindings);
+/// assert_eq!(bindings.len(), 1);
+/// assert_eq!(bindings["Jump"], "Space");
+/// ```
+#[cfg(feature = "strum")]
+pub fn retain_enum_keys<K, V>(map: &mut HashMap<String, V>)
+where
+    K: FromStr,
+{
+    map.retain(|key, _| key.parse::<K>().is_ok());
+}
+
+/// Asserts that every string key of a map parses to a variant of an enum.
+///
+/// The companion of [`validate_hash_map!`] for intentionally sparse maps: missing variants are fine, only keys
+/// that aren't variants are rejected. Keys are parsed with [`FromStr`](std::str::FromStr), e.g. derived with
+/// [`strum::EnumString`], so the `strum` feature isn't needed. See [`retain_enum_keys`] to drop such keys
+/// instead.
+///
+/// # Panics
+///
+/// Panics, listing the keys that fail to parse, if there are any.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::assert_no_extra_keys;
+/// use bevy_utils::HashMap;
+/// use strum::EnumString;
+///
+/// #[derive(Debug, EnumString)]
+/// enum Action {
+///     Jump,
+///     Crouch,
+/// }
+///
+/// let mut bindings = HashMap::new();
+/// bindings.insert("Jump".to_string(), "Space");
+///
+/// assert_no_extra_keys!(&bindings, Action);
+/// ```
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! assert_no_extra_keys {
+    ($map: expr, $enum: ty) => {{
+        let mut extra = ::std::vec::Vec::new();
+        for key in $map.keys() {
+            if key.parse::<$enum>().is_err() {
+                extra.push(key);
+            }
+        }
+        extra.sort();
+        assert!(
+            extra.is_empty(),
+            "hash map has keys that aren't variants of `{}`: {:?}",
+            ::core::stringify!($enum),
+            extra
+        );
+    }};
+}
+
+/// Validates that a given map contains exactly the expected keys.
+///
+/// This generalizes [`validate_hash_map`] beyond enums: the expected keys come from any iterator (e.g. a list
+/// read from a config file), so the `strum` feature isn't needed. Returns `true` if every expected key is
+/// present and the map has no other keys. Duplicates in `expected` are counted once.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::validate_coverage;
+/// use bevy_utils::HashMap;
+///
+/// let mut volumes = HashMap::new();
+/// volumes.insert("music", 0.5);
+/// volumes.insert("effects", 1.0);
+///
+/// assert!(validate_coverage(&volumes, ["music", "effects"]));
+/// assert!(!validate_coverage(&volumes, ["music", "effects", "voice"]));
+/// ```
+#[cfg(feature = "std")]
+pub fn validate_coverage<M, I>(map: &M, expected: I) -> bool
+where
+    M: MapKeys,
+    M::Key: Eq + Hash,
+    I: IntoIterator<Item = M::Key>,
+{
+    let expected = expected.into_iter().collect::<bevy_utils::HashSet<_>>();
+    expected.len() == map.key_count() && expected.iter().all(|key| map.contains_key(key))
+}
+
+/// Validates that a given map contains exactly the listed keys.
+///
+/// A feature-free alternative to [`validate_hash_map`]: instead of asking `strum` for the variants, the expected
+/// keys are written out, so it works for any key type implementing [`MapKeys`]. The expected length is the
+/// length of the list. Evaluates to `true` if every listed key is present and the map has no other keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::validate_keys;
+/// use bevy_utils::HashMap;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum LevelState {
+///     Level1,
+///     Level2,
+/// }
+///
+/// let mut scenes = HashMap::new();
+/// scenes.insert(LevelState::Level1, "level_1.scn");
+///
+/// assert!(!validate_keys!(&scenes, [LevelState::Level1, LevelState::Level2]));
+///
+/// scenes.insert(LevelState::Level2, "level_2.scn");
+///
+/// assert!(validate_keys!(&scenes, [LevelState::Level1, LevelState::Level2]));
+/// ```
+///
+/// # Notes
+///
+/// - Unlike [`validate_hash_map`], a variant added to the enum later isn't noticed until it's listed.
+///
+/// # Panics
+///
+/// Panics if the same key is listed more than once, since the map would then pass with a missing key in its
+/// place. Keys must implement [`PartialEq`] and [`Debug`](std::fmt::Debug).
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! validate_keys {
+    ($map: expr, [$(,)?]) => {
+        $crate::MapKeys::key_count($map) == 0
+    };
+    ($map: expr, [$( $key: expr ),+ $(,)?]) => {{
+        let map = $map;
+        let keys = [$( $key ),*];
+        for (index, key) in keys.iter().enumerate() {
+            assert!(!keys[..index].contains(key), "duplicate key in validate_keys!: {:?}", key);
         }
+        $crate::MapKeys::key_count(map) == [$( ::core::stringify!($key) ),*].len()
+            && keys.iter().all(|key| $crate::MapKeys::contains_key(map, key))
+    }};
+}
+
+/// Asserts that a given hash map contains exactly one of each possible key.
+///
+/// This macro is a convenience wrapper around the [`validate_hash_map`] function, intended to be used in 
+/// tests or other scenarios where you want to ensure that a `HashMap` is fully populated with no missing 
+/// or extra elements and panic otherwise. It's equivalent to `assert!(validate_hash_map(&hash_map));`.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// use bevy_hectic_utils::validate_hash_map;
+/// 
+/// validate_hash_map!(&hash_map);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the `hash_map` does not contain exactly one of each possible key
+/// or if the `hash_map` contains more than one of any key.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::validate_hash_map;
+/// use bevy_utils::HashMap;
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum LevelState {
+///     Level1,
+///     Level2,
+/// }
+/// 
+/// fn load_level_1(/* ... */) {
+/// // ...
+/// }
+/// 
+/// fn load_level_2(/* ... */) {
+/// // ...
+/// }
+/// 
+/// fn main() {
+///     let mut hash_map: HashMap<LevelState, fn()> = HashMap::new();
+///     hash_map.insert(LevelState::Level1, load_level_1);
+///     hash_map.insert(LevelState::Level2, load_level_2);
+///     
+///     validate_hash_map!(&hash_map); // Panics otherwise
+/// 
+///     // Code where you sure that the hash map contains exactly one of each possible key ...
+/// }
+/// ```
+#[macro_export]
+#[cfg(feature = "strum")]
+macro_rules! validate_hash_map {
+    ($hash_map:expr) => {
+        assert!($crate::validate_hash_map($hash_map));
+    };
+}
+
+/// Creates a [`HashMap`](bevy_utils::HashMap) holding a value for every variant of an enum.
+///
+/// Every variant is first filled with a clone of `default`, then the listed pairs override their keys, so
+/// the resulting map always passes [`validate_hash_map`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::{enum_hashmap, validate_hash_map};
+/// use strum::EnumIter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+/// enum Difficulty {
+///     Easy,
+///     Normal,
+///     Hard,
+/// }
+///
+/// let lives = enum_hashmap!{
+///     default: 3;
+///     Difficulty::Easy => 5,
+/// };
+///
+/// assert_eq!(lives[&Difficulty::Easy], 5);
+/// assert_eq!(lives[&Difficulty::Hard], 3);
+/// assert!(validate_hash_map(&lives));
+/// ```
+///
+/// # Notes
+///
+/// - Without listed pairs, the key type can't be inferred and must be annotated.
+/// - Keys must be variants yielded by [`IntoEnumIterator::iter`](strum::IntoEnumIterator::iter),
+///   otherwise the map no longer passes validation.
+///
+#[macro_export]
+#[cfg(feature = "strum")]
+macro_rules! enum_hashmap {
+    (default: $default: expr; $( $key: expr => $val: expr ),* $(,)?) => {{
+        let default = $default;
+        #[allow(unused_mut)]
+        let mut map = $crate::build_enum_map(|_| ::core::clone::Clone::clone(&default));
+        $( map.insert($key, $val); )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](bevy_utils::HashMap) using Bevy's hash maps for increased speed with less security.
+///
+/// This macro initializes a [`HashMap`](bevy_utils::HashMap) with the specified key-value pairs. It is called "no secure" (ns)
+/// because it prioritizes performance potentially at the cost of certain security measures found
+/// in other hash maps. It's a convenient way to quickly create a populated [`HashMap`](bevy_utils::HashMap).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::ns_hashmap;
+/// 
+/// let fruits = ns_hashmap!{
+///     "apple" => 1,
+///     "banana" => 2
+/// };
+/// 
+/// // `fruits` is now a HashMap containing {"apple": 1, "banana": 2}
+/// ```
+///
+/// # Notes
+///
+/// - This macro is a simple and performant way to instantiate a hash map, but it should not be used
+///   in security-sensitive contexts due to its lack of secure hashing.
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! ns_hashmap {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        let mut map = $crate::__private::bevy_utils::HashMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) from a list of key-value pairs.
+///
+/// This macro simplifies the creation of a [`HashMap`](`std::collections::HashMap`) by allowing inline definition of key-value pairs.
+/// It initializes a [`HashMap`](`std::collections::HashMap`) using Rust's standard [`std::collections::HashMap`] and inserts the specified
+/// pairs into the map. It's a convenient way to quickly create and populate a [`HashMap`](`std::collections::HashMap`).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap;
+/// use std::collections::HashMap;
+/// 
+/// let capitals: HashMap<&str, &str> = hashmap!{
+///     "France" => "Paris",
+///     "Spain" => "Madrid"
+/// };
+/// 
+/// // `capitals` is now a HashMap containing {"France": "Paris", "Spain": "Madrid"}
+/// ```
+///
+/// When the key and value types can't be inferred, they can be given up front, separated from the
+/// pairs by `;`:
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap;
+///
+/// let empty = hashmap!(String, u32;);
+/// let scores = hashmap!(String, u32; "alice".into() => 1, "bob".into() => 2);
+///
+/// assert!(empty.is_empty());
+/// assert_eq!(scores["alice"], 1);
+/// ```
+///
+/// # Notes
+///
+/// - This macro creates a mutable `HashMap` and fills it with the provided key-value pairs.
+/// - It is a shorthand for manually creating a `HashMap` and inserting each key and value.
+/// - It needs std, [`hashbrown_map!`] builds a `hashbrown::HashMap` without it.
+/// - Values can be any expression, including blocks, `if`/`else` and closures. Closures have distinct types,
+///   so a map of several closures needs its value type given up front (e.g. `fn(i32) -> i32`).
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! hashmap {
+    ($key_type: ty, $val_type: ty; $( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map: $crate::__private::collections::HashMap<$key_type, $val_type> = $crate::__private::collections::HashMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        let mut map = $crate::__private::collections::HashMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](bevy_utils::HashMap) with at least the given capacity, using Bevy's hash maps.
+///
+/// This is [`ns_hashmap!`] with the capacity expression first, separated from the pairs by `;`.
+/// Pre-sizing avoids rehashing for large literals or maps that will grow.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::ns_hashmap_with_capacity;
+/// 
+/// let fruits = ns_hashmap_with_capacity!(16; "apple" => 1, "banana" => 2);
+/// 
+/// assert!(fruits.capacity() >= 16);
+/// ```
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! ns_hashmap_with_capacity {
+    ($capacity: expr; $( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::bevy_utils::HashMap::with_capacity($capacity);
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`SeededHashMap`](hashmap::SeededHashMap) whose iteration order only depends on the seed.
+///
+/// This is [`ns_hashmap!`] with the seed expression first, separated from the pairs by `;`, see
+/// [`seeded_hashmap`](hashmap::seeded_hashmap).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::seeded_hashmap;
+/// 
+/// let first = seeded_hashmap!(42; "apple" => 1, "banana" => 2, "cherry" => 3);
+/// let second = seeded_hashmap!(42; "apple" => 1, "banana" => 2, "cherry" => 3);
+/// 
+/// assert!(first.iter().eq(second.iter()));
+/// ```
+///
+/// # Notes
+///
+/// - Iteration order is reproducible for the same seed and the same inserts in the same order.
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! seeded_hashmap {
+    ($seed: expr; $( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::hashmap::seeded_hashmap($seed);
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) with at least the given capacity.
+///
+/// This is [`hashmap!`] with the capacity expression first, separated from the pairs by `;`.
+/// Pre-sizing avoids rehashing for large literals or maps that will grow.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap_with_capacity;
+/// use std::collections::HashMap;
+/// 
+/// let capitals: HashMap<&str, &str> = hashmap_with_capacity!(16; "France" => "Paris");
+/// 
+/// assert!(capitals.capacity() >= 16);
+/// ```
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! hashmap_with_capacity {
+    ($capacity: expr; $( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::collections::HashMap::with_capacity($capacity);
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) mapping each element to itself.
+///
+/// Shorthand for [`hashmap!`] with `x => x` pairs. Each expression is evaluated once, and its value is cloned
+/// for the key.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap_ident;
+/// 
+/// let names = hashmap_ident!{ "alice", "bob" };
+/// 
+/// assert_eq!(names["bob"], "bob");
+/// ```
+///
+/// # Notes
+///
+/// - Elements must implement [`Clone`].
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! hashmap_ident {
+    ($( $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::collections::HashMap::new();
+        $(
+            let value = $val;
+            map.insert(::core::clone::Clone::clone(&value), value);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) from a list of key-value pairs, converting every value with
+/// [`TryInto`].
+///
+/// Evaluates to `Ok` with the map if every conversion succeeds, otherwise to the error of the first failing
+/// conversion, without evaluating the remaining pairs.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::try_hashmap;
+/// use std::collections::HashMap;
+/// 
+/// let volumes: Result<HashMap<&str, u8>, _> = try_hashmap!{
+///     "music" => 80i32,
+///     "effects" => 300i32,
+/// };
+/// 
+/// assert!(volumes.is_err());
+/// ```
+///
+/// # Notes
+///
+/// - The target value type usually has to be annotated, as with [`TryInto::try_into`].
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! try_hashmap {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::collections::HashMap::new();
+        #[allow(unused_labels)]
+        let result = 'convert: {
+            $(
+                match ::core::convert::TryInto::try_into($val) {
+                    ::core::result::Result::Ok(value) => {
+                        map.insert($key, value);
+                    }
+                    ::core::result::Result::Err(error) => break 'convert ::core::result::Result::Err(error),
+                }
+            )*
+            ::core::result::Result::Ok(map)
+        };
+        result
+    }};
+}
+
+/// Creates a [`HashMap`](`std::collections::HashMap`) from a list of key-value pairs, panicking on duplicate keys.
+///
+/// [`hashmap!`] silently keeps the last value of a duplicated key, which hides copy-paste bugs in large
+/// literals. This macro builds the same map but panics, naming the offending key, if a key is inserted twice.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashmap_checked;
+/// use std::collections::HashMap;
+/// 
+/// let capitals: HashMap<&str, &str> = hashmap_checked!{
+///     "France" => "Paris",
+///     "Spain" => "Madrid",
+/// };
+/// ```
+///
+/// # Panics
+///
+/// Panics if the same key is given more than once. Keys must implement [`Debug`](std::fmt::Debug).
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! hashmap_checked {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::collections::HashMap::new();
+        $(
+            match map.entry($key) {
+                $crate::__private::collections::hash_map::Entry::Occupied(entry) => {
+                    panic!("duplicate key in hashmap_checked!: {:?}", entry.key())
+                }
+                $crate::__private::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert($val);
+                }
+            }
+        )*
+        map
+    }};
+}
+
+/// Creates a [`BTreeMap`](`std::collections::BTreeMap`) from a list of key-value pairs.
+///
+/// This macro mirrors [`hashmap!`] but builds a [`std::collections::BTreeMap`], so the resulting map
+/// iterates in sorted key order. It's useful for deterministic output in tests and for configs that need
+/// sorted keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::btreemap;
+/// use std::collections::BTreeMap;
+/// 
+/// let versions: BTreeMap<&str, u32> = btreemap!{
+///     "serde" => 1,
+///     "bevy" => 12,
+/// };
+/// 
+/// // `versions` iterates as [("bevy", 12), ("serde", 1)]
+/// ```
+///
+/// # Notes
+///
+/// - As with [`BTreeMap::insert`](`std::collections::BTreeMap::insert`), a duplicated key keeps the last value.
+/// - It doesn't need std: std's `BTreeMap` is `alloc`'s, so the map is the same type in `#![no_std]` crates.
+///
+#[macro_export]
+macro_rules! btreemap {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::BTreeMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`phf::Map`] from a list of key-value pairs at compile time.
+///
+/// Unlike [`hashmap!`], the perfect hash function is computed by the compiler, so the map can initialize a
+/// `static` and lookups never build anything at runtime. It's meant for read-only tables known up front.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::static_map;
+///
+/// static KEY_BINDINGS: phf::Map<&'static str, char> = static_map!{
+///     "jump" => ' ',
+///     "crouch" => 'c',
+/// };
+///
+/// assert_eq!(KEY_BINDINGS.get("jump"), Some(&' '));
+/// assert_eq!(KEY_BINDINGS.get("fly"), None);
+/// ```
+///
+/// # Notes
+///
+/// - Keys must be literals (strings, byte strings, chars, integers or booleans), values any constant expressions.
+/// - Duplicate keys are a compile error.
+///
+#[macro_export]
+#[cfg(feature = "phf")]
+macro_rules! static_map {
+    ($( $key: literal => $val: expr ),* $(,)?) => {{
+        // `phf_map!` expands to a path relative to `phf`, which the caller may not depend on
+        use $crate::__private::phf;
+        phf::phf_map! { $( $key => $val ),* }
+    }};
+}
+
+/// Creates an [`IndexMap`](indexmap::IndexMap) from a list of key-value pairs.
+///
+/// This macro mirrors [`hashmap!`] but builds an [`indexmap::IndexMap`], so the resulting map iterates in
+/// insertion order. It's useful for UI menus and anything else user-facing.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::ordered_hashmap;
+/// use indexmap::IndexMap;
+/// 
+/// let menu: IndexMap<&str, u32> = ordered_hashmap!{
+///     "New game" => 1,
+///     "Continue" => 2,
+///     "Quit" => 3,
+/// };
+/// 
+/// assert_eq!(menu.keys().copied().collect::<Vec<_>>(), ["New game", "Continue", "Quit"]);
+/// ```
+///
+/// # Notes
+///
+/// - A duplicated key keeps its first position and the last value, as with
+///   [`IndexMap::insert`](indexmap::IndexMap::insert).
+///
+#[macro_export]
+#[cfg(feature = "indexmap")]
+macro_rules! ordered_hashmap {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::indexmap::IndexMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`HashSet`](bevy_utils::HashSet) using Bevy's hash sets for increased speed with less security.
+///
+/// This is the set counterpart of [`ns_hashmap!`]: it initializes a [`HashSet`](bevy_utils::HashSet) with the
+/// specified values using Bevy's fast, non-secure hasher.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::ns_hashset;
+/// 
+/// let fruits = ns_hashset!{ "apple", "banana", "apple" };
+/// 
+/// // `fruits` is now a HashSet containing {"apple", "banana"}
+/// assert_eq!(fruits.len(), 2);
+/// ```
+///
+/// # Notes
+///
+/// - Like [`ns_hashmap!`], this macro should not be used in security-sensitive contexts due to its lack of
+///   secure hashing.
+/// - Duplicated values are inserted only once.
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! ns_hashset {
+    ($( $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut set = $crate::__private::bevy_utils::HashSet::new();
+        $(
+            set.insert($val);
+        )*
+        set
+    }};
+}
+
+/// Creates a [`HashSet`](`std::collections::HashSet`) from a list of values.
+///
+/// This is the set counterpart of [`hashmap!`]: it initializes a [`std::collections::HashSet`] and inserts
+/// the specified values into it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_hectic_utils::hashset;
+/// use std::collections::HashSet;
+/// 
+/// let languages: HashSet<&str> = hashset!{ "rust", "c", "rust" };
+/// 
+/// // `languages` is now a HashSet containing {"rust", "c"}
+/// assert_eq!(languages.len(), 2);
+/// ```
+///
+/// # Notes
+///
+/// - Duplicated values are inserted only once.
+///
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! hashset {
+    ($( $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut set = $crate::__private::collections::HashSet::new();
+        $(
+            set.insert($val);
+        )*
+        set
+    }};
+}
+
+
+/// Creates a [`hashbrown::HashMap`] from a list of key-value pairs, without std.
+///
+/// This is [`hashmap!`] for `#![no_std]` crates using `alloc`, enabled by the `no_std` feature. Build the crate
+/// with `default-features = false` to leave std out entirely. The map macros relying on std keep building std
+/// maps when the feature is enabled too, so enabling it never changes their types.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "no_std")] {
+/// use bevy_hectic_utils::hashbrown_map;
+/// use hashbrown::HashMap;
+///
+/// let capitals: HashMap<&str, &str> = hashbrown_map!{
+///     "France" => "Paris",
+///     "Spain" => "Madrid"
+/// };
+/// let empty = hashbrown_map!(&str, u32;);
+///
+/// assert_eq!(capitals["Spain"], "Madrid");
+/// assert!(empty.is_empty());
+/// # }
+/// ```
+///
+#[macro_export]
+#[cfg(feature = "no_std")]
+macro_rules! hashbrown_map {
+    ($key_type: ty, $val_type: ty; $( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map: $crate::__private::hashbrown::HashMap<$key_type, $val_type> = $crate::__private::hashbrown::HashMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        let mut map = $crate::__private::hashbrown::HashMap::new();
+        $(
+            map.insert($key, $val);
+        )*
+        map
+    }};
+}
+
+/// Creates a [`hashbrown::HashMap`] from a list of key-value pairs, panicking on duplicated keys, without std.
+///
+/// This is [`hashmap_checked!`] for `#![no_std]` crates, see [`hashbrown_map!`].
+///
+/// # Panics
+///
+/// Panics if the same key is given more than once. Keys must implement [`Debug`](core::fmt::Debug).
+///
+#[macro_export]
+#[cfg(feature = "no_std")]
+macro_rules! hashbrown_map_checked {
+    ($( $key: expr => $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::__private::hashbrown::HashMap::new();
+        $(
+            match map.entry($key) {
+                $crate::__private::hashbrown::hash_map::Entry::Occupied(entry) => {
+                    panic!("duplicate key in hashbrown_map_checked!: {:?}", entry.key())
+                }
+                $crate::__private::hashbrown::hash_map::Entry::Vacant(entry) => {
+                    entry.insert($val);
+                }
+            }
+        )*
+        map
+    }};
+}
+
+/// Creates a [`hashbrown::HashSet`] from a list of values, without std.
+///
+/// This is [`hashset!`] for `#![no_std]` crates, see [`hashbrown_map!`].
+///
+#[macro_export]
+#[cfg(feature = "no_std")]
+macro_rules! hashbrown_set {
+    ($( $val: expr ),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut set = $crate::__private::hashbrown::HashSet::new();
+        $(
+            set.insert($val);
+        )*
+        set
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn hashmap_builds_std_hash_map() {
+        let map: std::collections::HashMap<&str, i32> = hashmap! {
+            "a" => 1,
+            "b" => 2
+        };
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("b"), Some(&2));
     }
 
-    impl Into<usize> for Times {
-        fn into(self) -> usize {
-            self.0 as usize
-        }
+    #[test]
+    fn hashmap_accepts_trailing_comma() {
+        let with_comma: std::collections::HashMap<&str, i32> = hashmap! {
+            "a" => 1,
+            "b" => 2,
+        };
+        let without_comma: std::collections::HashMap<&str, i32> = hashmap! {
+            "a" => 1,
+            "b" => 2
+        };
+
+        assert_eq!(with_comma, without_comma);
+        assert_eq!(with_comma.len(), 2);
     }
 
-    impl Into<u32> for Times {
-        fn into(self) -> u32 {
-            self.0 as u32
-        }
+    #[test]
+    fn ns_hashmap_accepts_trailing_comma() {
+        use bevy_utils::HashMap;
+
+        let with_comma: HashMap<&str, i32> = ns_hashmap! {
+            "a" => 1,
+            "b" => 2,
+        };
+        let without_comma: HashMap<&str, i32> = ns_hashmap! {
+            "a" => 1,
+            "b" => 2
+        };
+
+        assert_eq!(with_comma, without_comma);
+        assert_eq!(with_comma.get("a"), Some(&1));
+        assert_eq!(with_comma.get("b"), Some(&2));
     }
 
-    impl Into<i32> for Times {
-        fn into(self) -> i32 {
-            self.0 as i32
-        }
+    #[test]
+    fn hashmap_with_type_annotation() {
+        let empty = hashmap!(String, u32;);
+        let populated = hashmap!(String, u32; "a".into() => 1, "b".into() => 2,);
+
+        assert!(empty.is_empty());
+        assert_eq!(populated.len(), 2);
+        assert_eq!(populated.get("a"), Some(&1u32));
+        assert_eq!(populated.get("b"), Some(&2u32));
     }
 
-    impl From<u64> for Times {
-        fn from(times: u64) -> Self {
-            Self(times)
-        }
+    #[test]
+    fn hashmap_expression_values() {
+        let base = 10;
+        let map = hashmap! {
+            "block" => {
+                let doubled = base * 2;
+                doubled + 1
+            },
+            "if" => if base > 5 { 1 } else { 2 },
+            "match" => match base { 10 => 3, _ => 4 },
+            "method chain" => "1,2,3".split(',').map(|digit| digit.parse::<i32>().unwrap()).sum(),
+        };
+
+        assert_eq!(map, hashmap! { "block" => 21, "if" => 1, "match" => 3, "method chain" => 6 });
     }
 
-    impl From<usize> for Times {
-        fn from(times: usize) -> Self {
-            Self(times as u64)
-        }
+    #[test]
+    fn hashmap_closure_values() {
+        let offset = 3;
+        let ops = hashmap!(&str, Box<dyn Fn(i32) -> i32>;
+            "add" => Box::new(move |x| x + offset),
+            "square" => Box::new(|x| x * x),
+        );
+        let fns = hashmap!(&str, fn(i32) -> i32; "negate" => |x| -x, "double" => |x| x * 2);
+
+        assert_eq!(ops["add"](1), 4);
+        assert_eq!(ops["square"](3), 9);
+        assert_eq!(fns["negate"](1), -1);
+        assert_eq!(fns["double"](4), 8);
     }
 
-    impl From<u32> for Times {
-        fn from(times: u32) -> Self {
-            Self(times as u64)
-        }
+    #[test]
+    fn hashmap_with_capacity() {
+        let map: std::collections::HashMap<&str, i32> = hashmap_with_capacity!(64; "a" => 1, "b" => 2,);
+
+        assert!(map.capacity() >= 64);
+        assert_eq!(map, hashmap! { "a" => 1, "b" => 2 });
     }
 
-    impl From<i32> for Times {
-        fn from(times: i32) -> Self {
-            Self(times as u64)
-        }
+    #[test]
+    fn ns_hashmap_with_capacity() {
+        let map: bevy_utils::HashMap<&str, i32> = ns_hashmap_with_capacity!(64; "a" => 1, "b" => 2);
+
+        assert!(map.capacity() >= 64);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("b"), Some(&2));
     }
 
-    impl Default for Times {
-        /// Value that may be enough for most cases
-        fn default() -> Self {
-            Self(100000)
-        }
+    #[test]
+    fn hashmap_checked_unique_keys() {
+        let map: std::collections::HashMap<&str, i32> = hashmap_checked! {
+            "a" => 1,
+            "b" => 2,
+        };
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("b"), Some(&2));
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate key in hashmap_checked!: \"b\"")]
+    fn hashmap_checked_duplicate_key() {
+        let _map: std::collections::HashMap<&str, i32> = hashmap_checked! {
+            "a" => 1,
+            "b" => 2,
+            "b" => 3,
+        };
+    }
+
+    #[test]
+    fn hashmap_ident_maps_to_itself() {
+        let map = hashmap_ident! { "a".to_string(), "b".to_string(), "c".to_string() };
+
+        assert_eq!(map.len(), 3);
+        assert!(map.iter().all(|(key, value)| key == value));
+        assert_eq!(map["b"], "b");
+    }
+
+    #[test]
+    fn try_hashmap_converts_values() {
+        let map: Result<std::collections::HashMap<&str, u8>, _> = try_hashmap! {
+            "a" => 1i32,
+            "b" => 255i32,
+        };
+
+        assert_eq!(map, Ok(hashmap! { "a" => 1, "b" => 255 }));
+    }
+
+    #[test]
+    fn try_hashmap_overflow() {
+        let mut evaluated = false;
+        let map: Result<std::collections::HashMap<&str, u8>, std::num::TryFromIntError> = try_hashmap! {
+            "a" => 1i32,
+            "b" => 300i32,
+            "c" => {
+                evaluated = true;
+                3i32
+            },
+        };
+
+        assert!(map.is_err());
+        assert!(!evaluated);
+    }
+
+    #[test]
+    fn btreemap_iterates_in_sorted_order() {
+        let map = btreemap! {
+            "c" => 3,
+            "a" => 1,
+            "b" => 2,
+        };
+
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![("a", 1), ("b", 2), ("c", 3)]);
+    }
+
+    #[test]
+    fn btreemap_keeps_last_value_for_duplicate_keys() {
+        let map = btreemap! {
+            "a" => 1,
+            "a" => 2
+        };
+
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn btreemap_empty() {
+        let map: std::collections::BTreeMap<&str, i32> = btreemap! {};
+
+        assert!(map.is_empty());
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn ordered_hashmap_keeps_insertion_order() {
+        let map = ordered_hashmap! {
+            "c" => 1,
+            "a" => 2,
+            "b" => 3,
+            "a" => 4,
+        };
+
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![("c", 1), ("a", 4), ("b", 3)]);
+    }
+
+    #[test]
+    fn hashset_dedups_values() {
+        let set: std::collections::HashSet<&str> = hashset! { "a", "b", "a", "c", "b", };
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains("a") && set.contains("b") && set.contains("c"));
+    }
+
+    #[test]
+    fn hashset_empty() {
+        let set: std::collections::HashSet<i32> = hashset! {};
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn ns_hashset_dedups_values() {
+        let set: bevy_utils::HashSet<i32> = ns_hashset! { 1, 2, 2, 3, 3, 3 };
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&1) && set.contains(&2) && set.contains(&3));
+    }
+
+    #[test]
+    fn ns_hashset_empty() {
+        let set: bevy_utils::HashSet<i32> = ns_hashset! {};
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn validate_coverage_exact_match() {
+        let map: std::collections::HashMap<&str, i32> = hashmap! { "a" => 1, "b" => 2 };
+
+        assert!(crate::validate_coverage(&map, ["b", "a"]));
+        assert!(crate::validate_coverage(&map, ["a", "b", "a"]));
+    }
+
+    #[test]
+    fn validate_coverage_missing_keys() {
+        let map: std::collections::HashMap<&str, i32> = hashmap! { "a" => 1 };
+
+        assert!(!crate::validate_coverage(&map, ["a", "b"]));
+    }
+
+    #[test]
+    fn validate_coverage_extra_keys() {
+        let map: std::collections::HashMap<&str, i32> = hashmap! { "a" => 1, "b" => 2, "c" => 3 };
+
+        assert!(!crate::validate_coverage(&map, ["a", "b"]));
+        assert!(!crate::validate_coverage(&map, []));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Key {
+        A,
+        B,
+        C,
+    }
+
+    #[test]
+    fn validate_keys_exact_match() {
+        let map: bevy_utils::HashMap<Key, i32> = ns_hashmap! { Key::B => 2, Key::A => 1, Key::C => 3 };
+        let empty: bevy_utils::HashMap<Key, i32> = bevy_utils::HashMap::new();
+
+        assert!(validate_keys!(&map, [Key::A, Key::B, Key::C]));
+        assert!(validate_keys!(&empty, []));
+    }
+
+    #[test]
+    fn validate_keys_missing_keys() {
+        let map: bevy_utils::HashMap<Key, i32> = ns_hashmap! { Key::A => 1, Key::B => 2 };
+
+        assert!(!validate_keys!(&map, [Key::A, Key::B, Key::C]));
+    }
+
+    #[test]
+    fn validate_keys_extra_keys() {
+        let map: std::collections::HashMap<Key, i32> = hashmap! { Key::A => 1, Key::B => 2, Key::C => 3 };
+
+        assert!(!validate_keys!(&map, [Key::A, Key::B]));
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate key in validate_keys!: A")]
+    fn validate_keys_duplicate_keys() {
+        let map: std::collections::HashMap<Key, i32> = hashmap! { Key::A => 1, Key::B => 2 };
+
+        let _ = validate_keys!(&map, [Key::A, Key::A]);
     }
 
-    /// Enable logging for debug
-    pub fn enable_loggings() {
-        use std::env;
-        use std::io::Write;
+    #[cfg(feature = "phf")]
+    #[test]
+    fn static_map_lookup() {
+        static CODES: phf::Map<&'static str, u16> = static_map! {
+            "ok" => 200,
+            "not found" => 404,
+        };
+        static EMPTY: phf::Map<u32, ()> = static_map! {};
+
+        assert_eq!(CODES.get("ok"), Some(&200));
+        assert_eq!(CODES["not found"], 404);
+        assert_eq!(CODES.get("teapot"), None);
+        assert_eq!(CODES.len(), 2);
+        assert!(EMPTY.get(&1).is_none());
+    }
+
+    #[cfg(feature = "strum")]
+    mod validation {
+        use bevy_utils::{HashMap, HashSet};
+        use strum::{EnumCount, EnumIter, EnumString};
+
+        use crate::{
+            build_enum_map, check_hash_map, collect_enum_values, default_enum_map, diff_hash_map, for_each_enum_value, retain_enum_keys,
+            try_build_enum_map, validate_btree_map, validate_hash_map, validate_hash_set, HashMapValidationError, OnMissing,
+        };
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumCount, EnumString)]
+        enum Level {
+            First,
+            Second,
+            Third,
+        }
+
+        #[test]
+        fn diff_fully_populated() {
+            let map: HashMap<Level, i32> = ns_hashmap! {
+                Level::First => 1,
+                Level::Second => 2,
+                Level::Third => 3,
+            };
+
+            let diff = diff_hash_map(&map);
+            assert!(diff.missing.is_empty());
+            assert_eq!(diff.extra, 0);
+            assert!(diff.is_complete());
+            assert!(validate_hash_map(&map));
+        }
+
+        #[test]
+        fn diff_partially_populated() {
+            let map: HashMap<Level, i32> = ns_hashmap! {
+                Level::Second => 2,
+            };
+
+            let diff = diff_hash_map(&map);
+            assert_eq!(diff.missing, vec![Level::First, Level::Third]);
+            assert!(!diff.is_complete());
+            assert!(!validate_hash_map(&map));
+        }
+
+        #[test]
+        fn diff_empty() {
+            let map: HashMap<Level, i32> = HashMap::new();
+
+            let diff = diff_hash_map(&map);
+            assert_eq!(diff.missing, vec![Level::First, Level::Second, Level::Third]);
+            assert!(!validate_hash_map(&map));
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter, EnumCount)]
+        enum Asset {
+            Texture,
+            Named(String),
+        }
+
+        #[test]
+        fn validate_non_copy_keys() {
+            let mut map: HashMap<Asset, i32> = HashMap::new();
+            map.insert(Asset::Texture, 1);
+            assert!(!validate_hash_map(&map));
+            assert_eq!(diff_hash_map(&map).missing, vec![Asset::Named(String::new())]);
+
+            map.insert(Asset::Named(String::new()), 2);
+            assert!(validate_hash_map(&map));
+        }
+
+        #[test]
+        fn check_complete_hash_map() {
+            let map = build_enum_map(|level: Level| level as i32);
+
+            assert_eq!(check_hash_map(&map), Ok(()));
+        }
+
+        #[test]
+        fn check_hash_map_missing_key() {
+            let mut map = build_enum_map(|level: Level| level as i32);
+            map.remove(&Level::Second);
+
+            let error = check_hash_map(&map).unwrap_err();
+            assert_eq!(error, HashMapValidationError::MissingKeys(vec![Level::Second]));
+            assert_eq!(error.to_string(), "hash map is missing keys: [Second]");
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+        enum Toggle {
+            On,
+            #[strum(disabled)]
+            Off,
+        }
+
+        #[test]
+        fn validate_hash_map_ignores_disabled_variant() {
+            let mut map = HashMap::new();
+            map.insert(Toggle::On, 1);
+            assert!(validate_hash_map(&map));
+
+            map.insert(Toggle::Off, 0);
+            assert!(!validate_hash_map(&map));
+
+            let diff = diff_hash_map(&map);
+            assert!(diff.missing.is_empty());
+            assert_eq!(diff.extra, 1);
+        }
+
+        #[test]
+        fn check_hash_map_wrong_length() {
+            let mut map = HashMap::new();
+            map.insert(Toggle::On, 1);
+            map.insert(Toggle::Off, 0);
+
+            let error = check_hash_map(&map).unwrap_err();
+            assert_eq!(error, HashMapValidationError::WrongLength { expected: 1, actual: 2 });
+            assert_eq!(error.to_string(), "hash map has 2 keys, expected 1");
+        }
+
+        #[test]
+        fn assert_enum_exhaustive_vec() {
+            assert_enum_exhaustive!(vec![Level::Third, Level::First, Level::Second], Level);
+            assert_eq!(crate::missing_enum_variants::<Level, _>(vec![Level::Third]), vec![Level::First, Level::Second]);
+        }
+
+        #[test]
+        fn assert_enum_exhaustive_hash_map() {
+            let map = build_enum_map(|level: Level| level as i32);
+
+            assert_enum_exhaustive!(map.keys(), Level);
+        }
+
+        #[test]
+        #[should_panic(expected = "collection is missing variants of Level: [Second]")]
+        fn assert_enum_exhaustive_missing_variant() {
+            let mut map = build_enum_map(|level: Level| level as i32);
+            map.remove(&Level::Second);
+
+            assert_enum_exhaustive!(map.keys(), Level);
+        }
+
+        #[test]
+        fn enum_variant_count_is_const() {
+            const COUNT: usize = enum_variant_count!(Level);
+            let values = [0; enum_variant_count!(Level)];
+
+            assert_eq!(COUNT, 3);
+            assert_eq!(values.len(), 3);
+        }
+
+        #[test]
+        fn build_enum_map_validates() {
+            let map = build_enum_map(|level: Level| level as i32);
+
+            assert!(validate_hash_map(&map));
+            assert_eq!(map[&Level::Third], 2);
+        }
+
+        #[test]
+        fn build_enum_map_calls_once_per_variant() {
+            let mut calls = Vec::new();
+            let map = build_enum_map(|level: Level| calls.push(level));
+
+            assert_eq!(map.len(), 3);
+            assert_eq!(calls, vec![Level::First, Level::Second, Level::Third]);
+        }
+
+        #[test]
+        fn default_enum_map_validates() {
+            let map: HashMap<Level, String> = default_enum_map();
+
+            assert!(validate_hash_map(&map));
+            assert!(map.values().all(|value| *value == String::default()));
+        }
+
+        #[test]
+        fn try_build_enum_map_all_ok() {
+            let map = try_build_enum_map(|level: Level| Ok::<_, String>(level as i32));
 
-        let _ = env::set_var("RUST_LOG", "debug");
-        // FIXME: colorize logs
-        // TODO: colorize thorwed args
-        let _ = env_logger::builder()
-            .is_test(true)
-            .format(|buf, record| {
-                let mut style = buf.style();
-                let level = record.level();
+            assert!(validate_hash_map(&map.unwrap()));
+        }
+
+        #[test]
+        fn try_build_enum_map_returns_first_error() {
+            let mut calls = 0;
+            let map = try_build_enum_map(|level: Level| {
+                calls += 1;
                 match level {
-                    Level::Trace => style.set_color(env_logger::fmt::Color::Magenta),
-                    Level::Debug => style.set_color(env_logger::fmt::Color::Blue),
-                    Level::Info => style.set_color(env_logger::fmt::Color::Green),
-                    Level::Warn => style.set_color(env_logger::fmt::Color::Yellow),
-                    Level::Error => style.set_color(env_logger::fmt::Color::Red),
-                };
-
-                writeln!(buf, "{}: {}", style.value(level), record.args())
-            })
-            .try_init();
-    }
-
-    /// Measure time of predicate
-    pub fn measure_time<F: Copy>(predicate: F, times: Times) -> Duration
-    where
-        F: FnOnce() -> (),
-    {
-        let start = Instant::now();
-        for _ in 0..times.clone().into() {
-            predicate();
-        }
-        let global_duration = start.elapsed();
-        global_duration / times.into()
-    }
-}
\ No newline at end of file
+                    Level::Second => Err(format!("cannot load {level:?}")),
+                    _ => Ok(level as i32),
+                }
+            });
+
+            assert_eq!(map, Err("cannot load Second".to_string()));
+            assert_eq!(calls, 2);
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+        enum Empty {}
+
+        #[test]
+        fn validate_btree_map_complete_and_incomplete() {
+            let mut map = std::collections::BTreeMap::new();
+            map.insert(Level::First, 1);
+            map.insert(Level::Second, 2);
+            assert!(!validate_btree_map(&map));
+
+            map.insert(Level::Third, 3);
+            assert!(validate_btree_map(&map));
+        }
+
+        #[test]
+        fn validate_btree_map_empty_enum() {
+            let map: std::collections::BTreeMap<Empty, i32> = std::collections::BTreeMap::new();
+
+            assert!(validate_btree_map(&map));
+        }
+
+        #[test]
+        fn validate_std_hash_map() {
+            let mut map = std::collections::HashMap::new();
+            map.insert(Level::First, 1);
+            map.insert(Level::Third, 3);
+            assert!(!validate_hash_map(&map));
+            assert_eq!(diff_hash_map(&map).missing, vec![Level::Second]);
+
+            map.insert(Level::Second, 2);
+            assert!(validate_hash_map(&map));
+        }
+
+        #[test]
+        fn retain_enum_keys_drops_invalid_keys() {
+            let mut map: HashMap<String, i32> = ns_hashmap! {
+                "First".to_string() => 1,
+                "Third".to_string() => 3,
+                "Fourth".to_string() => 4,
+                "first".to_string() => 0,
+            };
+
+            retain_enum_keys::<Level, _>(&mut map);
+            assert_eq!(map.len(), 2);
+            assert_eq!(map["First"], 1);
+            assert_eq!(map["Third"], 3);
+        }
+
+        #[test]
+        fn retain_enum_keys_keeps_valid_keys() {
+            let mut map: HashMap<String, i32> = ns_hashmap! {
+                "First".to_string() => 1,
+                "Second".to_string() => 2,
+            };
+
+            retain_enum_keys::<Level, _>(&mut map);
+            assert_eq!(map.len(), 2);
+        }
+
+        #[test]
+        fn enum_hashmap_overrides_default() {
+            let map = enum_hashmap! {
+                default: 0;
+                Level::First => 5,
+                Level::Third => 7,
+            };
+
+            assert!(validate_hash_map(&map));
+            assert_eq!(map, ns_hashmap! { Level::First => 5, Level::Second => 0, Level::Third => 7 });
+        }
+
+        #[test]
+        fn enum_hashmap_only_default() {
+            let map: HashMap<Level, String> = enum_hashmap! { default: "none".to_string(); };
+
+            assert!(validate_hash_map(&map));
+            assert!(map.values().all(|value| value == "none"));
+        }
+
+        static_assert_enum_map_keys!(Level, [First, Second, Third]);
+        static_assert_enum_map_keys!(Asset, [Named, Texture]);
+
+        #[test]
+        fn collect_enum_values_in_variant_order() {
+            let map = ns_hashmap! {
+                Level::Third => "third",
+                Level::First => "first",
+                Level::Second => "second",
+            };
+
+            assert_eq!(collect_enum_values(&map), Ok(vec!["first", "second", "third"]));
+        }
+
+        #[test]
+        fn collect_enum_values_missing_keys() {
+            let map = ns_hashmap! { Level::Second => "second" };
+
+            assert_eq!(collect_enum_values(&map), Err(vec![Level::First, Level::Third]));
+        }
+
+        #[test]
+        fn for_each_enum_value_in_variant_order() {
+            let map = ns_hashmap! {
+                Level::Third => 3,
+                Level::First => 1,
+                Level::Second => 2,
+            };
+
+            let mut visited = Vec::new();
+            for_each_enum_value(&map, OnMissing::Fail, |level, value| visited.push((level, *value))).unwrap();
+            assert_eq!(visited, vec![(Level::First, 1), (Level::Second, 2), (Level::Third, 3)]);
+        }
+
+        #[test]
+        fn for_each_enum_value_missing_keys() {
+            let map = ns_hashmap! { Level::Third => 3, Level::First => 1 };
+
+            let mut visited = Vec::new();
+            assert_eq!(for_each_enum_value(&map, OnMissing::Skip, |level, _| visited.push(level)), Ok(()));
+            assert_eq!(visited, vec![Level::First, Level::Third]);
+
+            visited.clear();
+            assert_eq!(for_each_enum_value(&map, OnMissing::Fail, |level, _| visited.push(level)), Err(Level::Second));
+            assert!(visited.is_empty());
+        }
+
+        #[test]
+        fn validate_hash_set_complete() {
+            let set = ns_hashset! { Level::Third, Level::First, Level::Second };
+
+            assert!(validate_hash_set(&set));
+        }
+
+        #[test]
+        fn validate_hash_set_incomplete() {
+            let set = ns_hashset! { Level::Third, Level::First };
+
+            assert!(!validate_hash_set(&set));
+            assert_eq!(diff_hash_map(&set).missing, vec![Level::Second]);
+            assert!(!validate_hash_map(&hashset! { Level::First }));
+        }
+
+        #[test]
+        fn validate_hash_set_extra_elements() {
+            let empty: HashSet<Empty> = ns_hashset! {};
+            assert!(validate_hash_set(&empty));
+            assert!(!validate_hash_set(&ns_hashset! { Toggle::On, Toggle::Off }));
+        }
+
+        #[test]
+        fn assert_no_extra_keys_sparse_map() {
+            let map: HashMap<String, i32> = ns_hashmap! { "Second".to_string() => 2 };
+            let empty = std::collections::HashMap::<String, i32>::new();
+
+            assert_no_extra_keys!(&map, Level);
+            assert_no_extra_keys!(&empty, Level);
+        }
+
+        #[test]
+        #[should_panic(expected = "hash map has keys that aren't variants of `Level`: [\"Fourth\", \"first\"]")]
+        fn assert_no_extra_keys_bogus_keys() {
+            let map: HashMap<String, i32> = ns_hashmap! {
+                "First".to_string() => 1,
+                "first".to_string() => 1,
+                "Fourth".to_string() => 4,
+            };
+
+            assert_no_extra_keys!(&map, Level);
+        }
+    }
+}
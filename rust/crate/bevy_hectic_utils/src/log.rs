@@ -0,0 +1,231 @@
+use std::io::Write;
+
+use ::log::{Level, LevelFilter};
+use env_logger::{fmt::Color, Builder, Env, WriteStyle};
+
+/// Enable colorized logging of records at `level` and more severe ones
+///
+/// `RUST_LOG` takes precedence over `level` when it is set.
+///
+/// Messages of errors and warnings are colored too, in a dimmed level color.
+/// Coloring is disabled entirely when the `NO_COLOR` environment variable is set to a non-empty value.
+pub fn enable_logging(level: LevelFilter) {
+    let _ = builder(level, WriteStyle::Auto).try_init();
+}
+
+/// Enable colorized logging for debug, captured by the test harness when called from tests
+pub fn enable_logging_debug() {
+    let _ = builder(LevelFilter::Debug, WriteStyle::Auto).is_test(true).try_init();
+}
+
+/// Logger of records at `level` unless `RUST_LOG` is set, coloring them in `style` unless `NO_COLOR` is set
+fn builder(level: LevelFilter, style: WriteStyle) -> Builder {
+    let mut builder = Builder::from_env(Env::default().default_filter_or(level.to_string()));
+    let style = if no_color() { WriteStyle::Never } else { style };
+    // `Auto` leaves the choice to `RUST_LOG_STYLE` and whether the target is a terminal
+    if style != WriteStyle::Auto {
+        builder.write_style(style);
+    }
+    builder.format(|buf, record| {
+        let level = record.level();
+        let color = match level {
+            Level::Trace => Color::Magenta,
+            Level::Debug => Color::Blue,
+            Level::Info => Color::Green,
+            Level::Warn => Color::Yellow,
+            Level::Error => Color::Red,
+        };
+
+        let mut level_style = buf.style();
+        level_style.set_color(color.clone());
+        let mut args_style = buf.style();
+        if matches!(level, Level::Error | Level::Warn) {
+            args_style.set_color(color).set_dimmed(true);
+        }
+
+        writeln!(buf, "{}: {}", level_style.value(level), args_style.value(record.args()))
+    });
+    builder
+}
+
+/// See <https://no-color.org>
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Installs the crate's colorized logger, see [`enable_logging`]
+///
+/// Bevy's `LogPlugin` installs a logger of its own, so disable it when using this plugin:
+/// only the first installed logger is used, and this plugin leaves an already installed one in place.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_app::App;
+/// use bevy_hectic_utils::log::LogColorPlugin;
+/// use log::LevelFilter;
+///
+/// App::new().add_plugins(LogColorPlugin::default().with_level(LevelFilter::Info).with_color(false));
+/// ```
+#[cfg(feature = "bevy")]
+#[derive(Debug, Clone)]
+pub struct LogColorPlugin {
+    level: LevelFilter,
+    color: bool,
+}
+
+#[cfg(feature = "bevy")]
+impl LogColorPlugin {
+    /// Log records at `level` and more severe ones, unless `RUST_LOG` says otherwise
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Enable or disable coloring, `NO_COLOR` disables it regardless
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl Default for LogColorPlugin {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            color: true,
+        }
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl bevy_app::Plugin for LogColorPlugin {
+    fn build(&self, _app: &mut bevy_app::App) {
+        let style = if self.color { WriteStyle::Auto } else { WriteStyle::Never };
+        let _ = builder(self.level, style).try_init();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process::Command};
+
+    use ::log::{Level, LevelFilter, Log, Metadata, Record};
+    use env_logger::WriteStyle;
+
+    use super::builder;
+    use crate::test_env::{lock_env, EnvVar};
+
+    #[test]
+    fn level_without_rust_log() {
+        let _env = lock_env();
+        let _rust_log = EnvVar::set("RUST_LOG", None);
+        let logger = builder(LevelFilter::Warn, WriteStyle::Auto).build();
+
+        assert_eq!(logger.filter(), LevelFilter::Warn);
+        assert!(logger.enabled(&Metadata::builder().level(Level::Warn).build()));
+        assert!(!logger.enabled(&Metadata::builder().level(Level::Info).build()));
+    }
+
+    /// Set in the child processes of [`in_child`]
+    const LOG_CHILD: &str = "BEVY_HECTIC_UTILS_TEST_LOG_CHILD";
+
+    /// Rerun `test` in a child process with `NO_COLOR` set to `no_color` and without `RUST_LOG`, returning its stderr
+    ///
+    /// Loggers of a process are installed once and pipe targets never get colors, so tests log in a child process to
+    /// its stderr. Returns `None` in the child, which the test should then log in.
+    fn in_child(test: &str, no_color: Option<&str>) -> Option<String> {
+        if env::var_os(LOG_CHILD).is_some() {
+            return None;
+        }
+
+        let mut child = Command::new(env::current_exe().unwrap());
+        child.args(["--exact", test, "--nocapture"]).env(LOG_CHILD, "1").env_remove("RUST_LOG");
+        match no_color {
+            Some(value) => child.env("NO_COLOR", value),
+            None => child.env_remove("NO_COLOR"),
+        };
+        let output = child.output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        Some(String::from_utf8(output.stderr).unwrap())
+    }
+
+    /// Log a message at error, warn and info level in the child of [`in_child`], always colored unless `NO_COLOR` is set
+    fn log_colored(test: &str, no_color: Option<&str>) -> Option<String> {
+        let output = in_child(test, no_color);
+        if output.is_none() {
+            let logger = builder(LevelFilter::Trace, WriteStyle::Always).build();
+            for level in [Level::Error, Level::Warn, Level::Info] {
+                logger.log(&Record::builder().level(level).args(format_args!("message")).build());
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn enable_logging_with_level() {
+        let Some(output) = in_child("log::tests::enable_logging_with_level", Some("1")) else {
+            super::enable_logging(LevelFilter::Warn);
+            ::log::error!("error");
+            ::log::warn!("warn");
+            ::log::info!("info");
+            ::log::debug!("debug");
+            return;
+        };
+        assert_eq!(output, "ERROR: error\nWARN: warn\n");
+    }
+
+    #[test]
+    fn no_color_disables_ansi_codes() {
+        let Some(output) = log_colored("log::tests::no_color_disables_ansi_codes", Some("1")) else {
+            return;
+        };
+        assert_eq!(output, "ERROR: message\nWARN: message\nINFO: message\n");
+    }
+
+    #[test]
+    fn colors_levels_and_dims_error_and_warn_messages() {
+        let Some(output) = log_colored("log::tests::colors_levels_and_dims_error_and_warn_messages", None) else {
+            return;
+        };
+        assert!(output.contains("\x1b["), "{output:?}");
+
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 3, "{output:?}");
+        // `2` is the dimmed attribute
+        assert!(lines[0].contains("\x1b[2m\x1b[31mmessage"), "{:?}", lines[0]);
+        assert!(lines[1].contains("\x1b[2m\x1b[33mmessage"), "{:?}", lines[1]);
+        assert!(lines[2].contains("\x1b[32mINFO"), "{:?}", lines[2]);
+        assert!(!lines[2].contains("\x1b[2m"), "{:?}", lines[2]);
+    }
+
+    #[test]
+    fn empty_no_color_keeps_colors() {
+        let Some(output) = log_colored("log::tests::empty_no_color_keeps_colors", Some("")) else {
+            return;
+        };
+        assert!(output.contains("\x1b["), "{output:?}");
+    }
+
+    #[test]
+    fn rust_log_overrides_level() {
+        let _env = lock_env();
+        let _rust_log = EnvVar::set("RUST_LOG", Some("error"));
+        let logger = builder(LevelFilter::Debug, WriteStyle::Auto).build();
+
+        assert_eq!(logger.filter(), LevelFilter::Error);
+        assert!(logger.enabled(&Metadata::builder().level(Level::Error).build()));
+        assert!(!logger.enabled(&Metadata::builder().level(Level::Debug).build()));
+    }
+
+    #[cfg(feature = "bevy")]
+    #[test]
+    fn log_color_plugin_in_app() {
+        let _env = lock_env();
+        let mut app = bevy_app::App::new();
+        app.add_plugins(super::LogColorPlugin::default().with_level(LevelFilter::Debug));
+
+        app.update();
+    }
+}
@@ -0,0 +1,36 @@
+//! Common macros, functions and types, meant to be glob imported
+//!
+//! ```rust
+//! use bevy_hectic_utils::prelude::*;
+//!
+//! let map = hashmap!{ "a" => 1 };
+//! let mean = measure_time(|| map.get("a"), Times::from(10));
+//! ```
+
+pub use crate::btreemap;
+#[cfg(feature = "std")]
+pub use crate::{
+    assert_faster_than, assert_maps_eq, assert_no_extra_keys, bench, hashmap, hashmap_checked, hashmap_ident,
+    hashmap_with_capacity, hashset, ns_hashmap, ns_hashmap_with_capacity, ns_hashset, seeded_hashmap, try_hashmap,
+    validate_keys,
+};
+#[cfg(feature = "std")]
+pub use crate::{
+    bench::{measure_stats, measure_time, timed, TimingStats},
+    hashmap::HashMapExt,
+    log::enable_logging,
+    validate_coverage, MapKeys, Times,
+};
+
+#[cfg(feature = "no_std")]
+pub use crate::{hashbrown_map, hashbrown_map_checked, hashbrown_set};
+#[cfg(feature = "indexmap")]
+pub use crate::ordered_hashmap;
+#[cfg(feature = "phf")]
+pub use crate::static_map;
+#[cfg(feature = "strum")]
+pub use crate::{
+    assert_enum_exhaustive, build_enum_map, check_hash_map, default_enum_map, diff_hash_map, enum_hashmap,
+    enum_variant_count, impl_enum_index, static_assert_enum_map_keys, try_build_enum_map, validate_hash_map,
+    validate_hash_set, EnumIndex, EnumIndexed, EnumMap, HashMapValidationError,
+};
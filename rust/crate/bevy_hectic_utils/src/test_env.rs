@@ -0,0 +1,41 @@
+//! Environment variables shared by tests of all modules
+
+use std::{
+    env,
+    ffi::OsString,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// Environment is shared by all tests, so tests reading or writing variables take turns
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Take the turn to use the environment, even if a test panicked during its turn
+pub(crate) fn lock_env() -> MutexGuard<'static, ()> {
+    ENV.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Environment variable set or removed until dropped, then restored, only use while holding [`lock_env`]
+pub(crate) struct EnvVar {
+    name: &'static str,
+    previous: Option<OsString>,
+}
+
+impl EnvVar {
+    pub(crate) fn set(name: &'static str, value: Option<&str>) -> Self {
+        let previous = env::var_os(name);
+        match value {
+            Some(value) => env::set_var(name, value),
+            None => env::remove_var(name),
+        }
+        Self { name, previous }
+    }
+}
+
+impl Drop for EnvVar {
+    fn drop(&mut self) {
+        match &self.previous {
+            Some(value) => env::set_var(self.name, value),
+            None => env::remove_var(self.name),
+        }
+    }
+}
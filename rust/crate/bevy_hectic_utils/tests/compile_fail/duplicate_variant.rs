@@ -0,0 +1,12 @@
+use bevy_hectic_utils::static_assert_enum_map_keys;
+use strum::EnumCount;
+
+#[derive(EnumCount)]
+enum Level {
+    First,
+    Second,
+}
+
+static_assert_enum_map_keys!(Level, [First, Second, Second]);
+
+fn main() {}
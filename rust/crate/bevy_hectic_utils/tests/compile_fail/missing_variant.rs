@@ -0,0 +1,13 @@
+use bevy_hectic_utils::static_assert_enum_map_keys;
+use strum::EnumCount;
+
+#[derive(EnumCount)]
+enum Level {
+    First,
+    Second,
+    Third,
+}
+
+static_assert_enum_map_keys!(Level, [First, Third]);
+
+fn main() {}
@@ -0,0 +1,38 @@
+//! `debug_hashmap!` logs through the global logger, which is installed once per test binary
+
+use std::sync::Mutex;
+
+use bevy_hectic_utils::debug_hashmap;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+struct Capture(Mutex<Vec<String>>);
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(format!("{}: {}", record.level(), record.args()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+#[test]
+fn logs_entries() {
+    let map = debug_hashmap! { "door" => 2, "chest" => 1 };
+    assert_eq!(map.len(), 2);
+    assert!(CAPTURE.0.lock().unwrap().is_empty());
+
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let map = debug_hashmap! { "door" => 2, "chest" => 1 };
+    assert_eq!(map["door"], 2);
+    assert_eq!(*CAPTURE.0.lock().unwrap(), [r#"DEBUG: {"chest" => 1, "door" => 2}"#]);
+}
@@ -0,0 +1,54 @@
+//! Macros must not rely on the caller importing anything but the macros themselves
+
+use bevy_hectic_utils::{
+    btreemap, hashmap, hashmap_checked, hashmap_ident, hashmap_with_capacity, hashset, ns_hashmap, ns_hashmap_with_capacity,
+    ns_hashset, seeded_hashmap, try_hashmap,
+};
+
+#[test]
+fn macros_without_imports() {
+    assert_eq!(ns_hashmap! { "a" => 1 }.len(), 1);
+    assert_eq!(hashmap! { "a" => 1 }.len(), 1);
+    assert_eq!(hashmap!(&str, i32; "a" => 1).len(), 1);
+    assert_eq!(hashmap_checked! { "a" => 1 }.len(), 1);
+    assert_eq!(hashmap_ident! { "a" }.len(), 1);
+    assert_eq!(hashmap_with_capacity!(4; "a" => 1).len(), 1);
+    assert_eq!(ns_hashmap_with_capacity!(4; "a" => 1).len(), 1);
+    assert_eq!(seeded_hashmap!(4; "a" => 1).len(), 1);
+    assert_eq!(btreemap! { "a" => 1 }.len(), 1);
+    assert_eq!(try_hashmap! { "a" => 1i32 }.map(|map: std::collections::HashMap<_, u8>| map.len()), Ok(1));
+    assert_eq!(ns_hashset! { "a" }.len(), 1);
+    assert_eq!(hashset! { "a" }.len(), 1);
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn ordered_hashmap_without_imports() {
+    assert_eq!(bevy_hectic_utils::ordered_hashmap! { "a" => 1 }.len(), 1);
+}
+
+#[cfg(feature = "phf")]
+#[test]
+fn static_map_without_imports() {
+    assert_eq!(bevy_hectic_utils::static_map! { "a" => 1 }.len(), 1);
+}
+
+#[cfg(feature = "strum")]
+#[test]
+fn enum_hashmap_without_imports() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumIter)]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    let map = bevy_hectic_utils::enum_hashmap! { default: 0; Side::Left => 1 };
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&Side::Right], 0);
+}
+
+#[test]
+fn bench_without_imports() {
+    let stats = bevy_hectic_utils::bench!("noop", 10, {});
+    assert!(stats.min <= stats.max);
+}
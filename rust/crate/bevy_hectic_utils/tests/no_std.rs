@@ -0,0 +1,28 @@
+//! With the `no_std` feature, the `hashbrown_*` map macros and `btreemap!` must expand without `std` in scope, so
+//! they can be used from `#![no_std]` crates
+//!
+//! Run with `cargo test --no-default-features --features no_std --test no_std`, which also builds the crate itself
+//! without std
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use bevy_hectic_utils::{btreemap, hashbrown_map, hashbrown_map_checked, hashbrown_set};
+use hashbrown::{HashMap, HashSet};
+
+#[test]
+fn macros_without_std() {
+    let map: HashMap<&str, i32> = hashbrown_map! { "a" => 1 };
+    assert_eq!(map.len(), 1);
+    let map: HashMap<&str, i32> = hashbrown_map!(&str, i32; "a" => 1);
+    assert_eq!(map.len(), 1);
+    let map: HashMap<&str, i32> = hashbrown_map_checked! { "a" => 1 };
+    assert_eq!(map.len(), 1);
+    let map: BTreeMap<&str, i32> = btreemap! { "a" => 1 };
+    assert_eq!(map.len(), 1);
+    let set: HashSet<&str> = hashbrown_set! { "a", "a" };
+    assert_eq!(set.len(), 1);
+}
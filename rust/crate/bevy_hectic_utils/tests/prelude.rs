@@ -0,0 +1,71 @@
+//! Everything in the prelude is usable with the prelude as the only import
+
+use bevy_hectic_utils::prelude::*;
+
+#[test]
+fn map_macros() {
+    let mut map = hashmap! { "a" => 1 };
+    *map.get_or_insert_default("b") += 2;
+
+    assert_maps_eq!(map, hashmap_checked! { "a" => 1, "b" => 2 });
+    assert_eq!(hashmap_ident! { 1 }[&1], 1);
+    assert_eq!(hashmap_with_capacity!(4; "a" => 1).len(), 1);
+    assert_eq!(try_hashmap! { "a" => 1i32 }.map(|map| map["a"]), Ok(1u8));
+    assert_eq!(ns_hashmap! { "a" => 1 }.len(), 1);
+    assert_eq!(ns_hashmap_with_capacity!(4; "a" => 1).len(), 1);
+    assert_eq!(seeded_hashmap!(4; "a" => 1).len(), 1);
+    assert_eq!(btreemap! { "a" => 1 }.len(), 1);
+    assert_eq!(hashset! { "a" }.len(), 1);
+    assert_eq!(ns_hashset! { "a" }.len(), 1);
+    assert!(validate_coverage(&map, ["a", "b"]));
+    assert_eq!(MapKeys::key_count(&map), 2);
+}
+
+#[test]
+fn timing() {
+    enable_logging(::log::LevelFilter::Off);
+    let (result, _) = timed(|| 42);
+    let stats: TimingStats = measure_stats(|| (), Times::from(10));
+
+    assert_eq!(result, 42);
+    assert!(stats.min <= stats.max);
+    assert!(measure_time(|| (), Times::from(10)) < std::time::Duration::from_secs(1));
+    assert!(bench!("noop", 10, {}).min <= std::time::Duration::from_secs(1));
+    assert_faster_than!(|| (), std::time::Duration::from_secs(1), 10);
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn ordered_map() {
+    assert_eq!(ordered_hashmap! { "a" => 1 }.len(), 1);
+}
+
+#[cfg(feature = "strum")]
+#[test]
+fn enum_helpers() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumIter, strum::EnumCount)]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    static_assert_enum_map_keys!(Side, [Left, Right]);
+    impl_enum_index!(Side);
+
+    let map = build_enum_map(|side: Side| side as u8);
+    validate_hash_map!(&map);
+    assert!(validate_hash_map(&map));
+    assert!(check_hash_map(&map).is_ok());
+    assert!(diff_hash_map(&map).is_complete());
+    assert_enum_exhaustive!(map.keys(), Side);
+    assert_eq!(enum_variant_count!(Side), 2);
+    assert_eq!(try_build_enum_map(|side: Side| Ok::<_, ()>(side as u8)), Ok(map.clone()));
+    assert_eq!(enum_hashmap! { default: 0; Side::Right => 1 }, map);
+    assert_eq!(default_enum_map::<Side, u8>()[&Side::Left], 0);
+    assert!(validate_hash_set(&ns_hashset! { Side::Left, Side::Right }));
+    assert_eq!(EnumMap::from(map.clone()).get(Side::Right), Some(&1));
+    assert_eq!(EnumIndexed::from_fn(|side: Side| side as u8 * 2)[Side::Right], 2);
+
+    let error: HashMapValidationError<Side> = HashMapValidationError::MissingKeys(vec![Side::Left]);
+    assert_eq!(error.to_string(), "hash map is missing keys: [Left]");
+}
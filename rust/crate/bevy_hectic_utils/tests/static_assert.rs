@@ -0,0 +1,26 @@
+//! Compile failures of `static_assert_enum_map_keys!`
+#![cfg(feature = "strum")]
+
+use std::{env, process::Command};
+
+/// Toolchain pinned in `rust/rust-toolchain.rs`, which the `.stderr` snapshots are generated with
+///
+/// Other compilers word the same errors differently, so the snapshots only match this one
+const SNAPSHOT_TOOLCHAIN: &str = "rustc 1.72.";
+
+fn is_snapshot_toolchain() -> bool {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    Command::new(rustc)
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.stdout.starts_with(SNAPSHOT_TOOLCHAIN.as_bytes()))
+}
+
+#[test]
+fn static_assert_enum_map_keys_rejects_mismatch() {
+    if !is_snapshot_toolchain() {
+        eprintln!("skipped, snapshots are generated with {SNAPSHOT_TOOLCHAIN}x");
+        return;
+    }
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,9 @@
+use bevy_hectic_utils::Times;
+
+#[test]
+fn times_is_public() {
+    let times = Times::default();
+
+    assert_eq!(times.get(), 100000);
+    assert_eq!(bevy_hectic_utils::bench::measure_samples(|| (), Times::from(3)).len(), 3);
+}